@@ -2,54 +2,107 @@ mod screens;
 pub mod ui;
 mod widgets;
 
-use crate::events::{
-    AppEvent, Event, EventHandler,
-    navigation::{NavDirection, Navigation},
+use crate::{
+    collection::{Collection, Mark},
+    config::Config,
+    events::{
+        AppEvent, Event, EventHandler,
+        command::{Command, Commands},
+        input::{TextEditing, TextInput},
+        navigation::{NavDirection, Navigation},
+        route::Route,
+    },
+    fetch::Scheduler,
 };
 use color_eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::DefaultTerminal;
-use screens::pokedex::PokedexScreen;
-use tokio::sync::mpsc::UnboundedSender;
+use screens::{
+    ability_detail::AbilityScreen, compare::CompareScreen, damage_calc::DamageCalcScreen, move_detail::MoveScreen, natures::NaturesScreen, pokedex::PokedexScreen,
+    pokemon::PokemonScreen, team::TeamScreen, type_chart::TypeChartScreen,
+};
 
 #[derive(Debug)]
 pub enum CurrentScreen {
     Pokedex(PokedexScreen),
+    TypeChart(TypeChartScreen),
+    Natures(NaturesScreen),
+    DamageCalc(DamageCalcScreen),
+    Team(TeamScreen),
+    Compare(CompareScreen),
+    Move(MoveScreen),
+    Ability(AbilityScreen),
+    Pokemon(PokemonScreen),
 }
 
 impl CurrentScreen {
-    fn new(sender: UnboundedSender<Event>) -> Self {
-        Self::Pokedex(PokedexScreen::new(sender))
+    /// Whether both show the same thing, pushing a screen on top of itself does nothing
+    fn is_same(&self, other: &CurrentScreen) -> bool {
+        match (self, other) {
+            (CurrentScreen::Pokedex(_), CurrentScreen::Pokedex(_)) => true,
+            (CurrentScreen::TypeChart(_), CurrentScreen::TypeChart(_)) => true,
+            (CurrentScreen::Natures(_), CurrentScreen::Natures(_)) => true,
+            (CurrentScreen::DamageCalc(_), CurrentScreen::DamageCalc(_)) => true,
+            (CurrentScreen::Team(_), CurrentScreen::Team(_)) => true,
+            (CurrentScreen::Compare(_), CurrentScreen::Compare(_)) => true,
+            (CurrentScreen::Move(a), CurrentScreen::Move(b)) => a.name() == b.name(),
+            (CurrentScreen::Ability(a), CurrentScreen::Ability(b)) => a.name() == b.name(),
+            (CurrentScreen::Pokemon(a), CurrentScreen::Pokemon(b)) => a.name() == b.name(),
+            _ => false,
+        }
     }
 }
+
 #[derive(Debug)]
 pub struct App {
     pub should_quit: bool,
     pub events: EventHandler,
+    /// Shared by every screen and widget so they all go through the same cache and connection pool
+    pub scheduler: Scheduler,
     pub current_screen: CurrentScreen,
+    /// Screens to go back to, the most recent last
+    back_stack: Vec<CurrentScreen>,
+    /// Screens left by going back, cleared when a new screen is pushed
+    forward_stack: Vec<CurrentScreen>,
+    /// Screens keep their state while another one is shown
+    pokedex: PokedexScreen,
+    type_chart: TypeChartScreen,
+    natures: NaturesScreen,
+    damage_calc: DamageCalcScreen,
+    team: TeamScreen,
+    compare: CompareScreen,
 }
 
-impl Default for App {
-    fn default() -> Self {
+impl App {
+    pub fn new(config: &Config) -> Result<Self> {
         let events = EventHandler::new();
-        Self {
+        let scheduler = Scheduler::new(events.sender.clone(), config)?;
+        let collection = Collection::load(config.data.dir.clone())?;
+        let pokedex = PokedexScreen::new(events.sender.clone(), scheduler.clone(), collection);
+        Ok(Self {
             should_quit: Default::default(),
-            current_screen: CurrentScreen::new(events.sender.clone()),
+            current_screen: CurrentScreen::Pokedex(pokedex.clone()),
+            back_stack: Vec::new(),
+            forward_stack: Vec::new(),
+            type_chart: TypeChartScreen::new(events.sender.clone()),
+            natures: NaturesScreen::new(events.sender.clone()),
+            damage_calc: DamageCalcScreen::new(events.sender.clone(), scheduler.clone()),
+            team: TeamScreen::new(events.sender.clone(), scheduler.clone()),
+            compare: CompareScreen::new(events.sender.clone(), scheduler.clone()),
+            pokedex,
+            scheduler,
             events,
-        }
+        })
     }
-}
 
-impl App {
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
-        match &self.current_screen {
-            CurrentScreen::Pokedex(dex) => dex.run(),
-        }
+        self.pokedex.run();
         self.focus();
         while !self.should_quit {
             match self.events.next().await? {
                 Event::Crossterm(event) => match event {
                     crossterm::event::Event::Key(key_event) => self.handle_key_events(key_event),
+                    crossterm::event::Event::Paste(text) => self.handle_paste(text),
                     _ => {
                         terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
                     }
@@ -62,6 +115,24 @@ impl App {
                     AppEvent::Navigation(direction) => {
                         self.handle_navigation_input(direction);
                     }
+                    AppEvent::Command(command) => {
+                        match command {
+                            _ if self.handle_command(command) => {}
+                            Command::Cancel if !self.back() => self.quit(),
+                            Command::DamageCalc => self.push(Route::DamageCalc { attacker: None, move_: None }),
+                            Command::Compare => self.push(Route::Compare(None)),
+                            _ => {}
+                        }
+                    }
+                    AppEvent::Push(route) => self.push(route),
+                    AppEvent::Replace(route) => self.replace(route),
+                    AppEvent::Back => {
+                        self.back();
+                    }
+                    AppEvent::Forward => {
+                        self.forward();
+                    }
+                    AppEvent::AddToTeam(addition) => self.team.add(addition),
                 },
             }
         }
@@ -69,11 +140,46 @@ impl App {
     }
 
     fn handle_key_events(&mut self, key_event: KeyEvent) {
+        if self.editing() {
+            self.handle_editing_key_events(key_event);
+            return;
+        }
         match key_event.code {
-            KeyCode::Esc | KeyCode::Char('q') => self.events.send(AppEvent::Quit),
+            KeyCode::Char('q') => self.events.send(AppEvent::Quit),
+            KeyCode::Esc => self.events.send(AppEvent::Command(Command::Cancel)),
+            KeyCode::Enter => self.events.send(AppEvent::Command(Command::Select)),
+            KeyCode::Char('d') => self.events.send(AppEvent::Command(Command::PickDex)),
+            KeyCode::Char('g') => self.events.send(AppEvent::Command(Command::CycleTypeChart)),
+            KeyCode::Char('v') => self.events.send(AppEvent::Command(Command::CycleVersionGroup)),
+            KeyCode::Char('f') => self.events.send(AppEvent::Command(Command::CycleForm)),
+            KeyCode::Char('s') => self.events.send(AppEvent::Command(Command::ToggleStatCalculator)),
+            KeyCode::Char('+' | '=') => self.events.send(AppEvent::Command(Command::Increase)),
+            KeyCode::Char('-') => self.events.send(AppEvent::Command(Command::Decrease)),
+            KeyCode::Char('t') => self.events.send(AppEvent::Push(Route::TypeChart)),
+            KeyCode::Char('n') => self.events.send(AppEvent::Push(Route::Natures)),
+            KeyCode::Char('p') => self.events.send(AppEvent::Push(Route::Team)),
+            KeyCode::Char('a') => self.events.send(AppEvent::Command(Command::AddToTeam)),
+            KeyCode::Char('x') => self.events.send(AppEvent::Command(Command::Compare)),
+            KeyCode::Char('o') => self.events.send(AppEvent::Command(Command::CycleSort)),
+            KeyCode::Char('O') => self.events.send(AppEvent::Command(Command::ReverseSort)),
+            KeyCode::Char('w') => self.events.send(AppEvent::Command(Command::ToggleColumns)),
+            KeyCode::Char('W') => self.events.send(AppEvent::Command(Command::WarmCache)),
+            KeyCode::Char('m') => self.events.send(AppEvent::Command(Command::ToggleMissingOnly)),
+            KeyCode::Char('i') => self.events.send(AppEvent::Command(Command::Import)),
+            KeyCode::Char('e') => self.events.send(AppEvent::Command(Command::Export)),
+            KeyCode::Char('r') | KeyCode::Delete => self.events.send(AppEvent::Command(Command::Remove)),
+            KeyCode::Backspace => self.events.send(AppEvent::Back),
+            KeyCode::Left if key_event.modifiers == KeyModifiers::ALT => self.events.send(AppEvent::Back),
+            KeyCode::Right if key_event.modifiers == KeyModifiers::ALT => {
+                self.events.send(AppEvent::Forward)
+            }
             KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
                 self.events.send(AppEvent::Quit)
             }
+            KeyCode::Char('c') => self.events.send(AppEvent::Command(Command::DamageCalc)),
+            KeyCode::Char('F') => self.events.send(AppEvent::Command(Command::Mark(Mark::Favorite))),
+            KeyCode::Char('S') => self.events.send(AppEvent::Command(Command::Mark(Mark::Seen))),
+            KeyCode::Char('C') => self.events.send(AppEvent::Command(Command::Mark(Mark::Caught))),
             KeyCode::Up | KeyCode::Char('k') => {
                 self.events.send(AppEvent::Navigation(NavDirection::Up))
             }
@@ -86,13 +192,125 @@ impl App {
             KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab=> self
                 .events
                 .send(AppEvent::Navigation(NavDirection::BackTab)),
+            KeyCode::Char('/') => {
+                self.start_editing();
+            }
             _ => {}
         }
     }
 
+    fn handle_editing_key_events(&mut self, key_event: KeyEvent) {
+        let input = match key_event.code {
+            KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
+                self.events.send(AppEvent::Quit);
+                return;
+            }
+            KeyCode::Up => {
+                self.events.send(AppEvent::Navigation(NavDirection::Up));
+                return;
+            }
+            KeyCode::Down => {
+                self.events.send(AppEvent::Navigation(NavDirection::Down));
+                return;
+            }
+            KeyCode::Char(c) => TextInput::Char(c),
+            KeyCode::Backspace => TextInput::Backspace,
+            KeyCode::Enter => TextInput::Submit,
+            KeyCode::Esc => TextInput::Cancel,
+            _ => return,
+        };
+        self.handle_text_input(input);
+    }
+
+    /// Pasted text goes into an open text box, or is imported as a team on the team screen
+    fn handle_paste(&mut self, text: String) {
+        if self.editing() {
+            for c in text.chars().filter(|c| !c.is_control()) {
+                self.handle_text_input(TextInput::Char(c));
+            }
+        } else if let CurrentScreen::Team(team) = &self.current_screen {
+            team.import_text(&text);
+        }
+    }
+
     fn quit(&mut self) {
         self.should_quit = true;
     }
+
+    fn open(&self, route: Route) -> CurrentScreen {
+        match route {
+            Route::TypeChart => CurrentScreen::TypeChart(self.type_chart.clone()),
+            Route::Natures => CurrentScreen::Natures(self.natures.clone()),
+            Route::Team => CurrentScreen::Team(self.team.clone()),
+            Route::Compare(pokemon) => {
+                if let Some(name) = pokemon {
+                    self.compare.pin(name);
+                }
+                CurrentScreen::Compare(self.compare.clone())
+            }
+            Route::DamageCalc { attacker, move_ } => {
+                self.damage_calc.prefill(attacker, move_);
+                CurrentScreen::DamageCalc(self.damage_calc.clone())
+            }
+            Route::Move(name) => {
+                let screen = MoveScreen::new(self.events.sender.clone(), self.scheduler.clone(), name);
+                screen.run();
+                CurrentScreen::Move(screen)
+            }
+            Route::Ability(name) => {
+                let screen = AbilityScreen::new(self.events.sender.clone(), self.scheduler.clone(), name);
+                screen.run();
+                CurrentScreen::Ability(screen)
+            }
+            Route::Pokemon(name) => {
+                let screen = PokemonScreen::new(self.events.sender.clone(), self.scheduler.clone(), name);
+                screen.run();
+                CurrentScreen::Pokemon(screen)
+            }
+        }
+    }
+
+    /// Shows `screen` and returns the one it replaced, screens keep their state while hidden
+    fn switch_to(&mut self, screen: CurrentScreen) -> CurrentScreen {
+        self.unfocus();
+        let previous = std::mem::replace(&mut self.current_screen, screen);
+        self.focus();
+        previous
+    }
+
+    fn push(&mut self, route: Route) {
+        let screen = self.open(route);
+        if screen.is_same(&self.current_screen) {
+            return;
+        }
+        let previous = self.switch_to(screen);
+        self.back_stack.push(previous);
+        self.forward_stack.clear();
+    }
+
+    fn replace(&mut self, route: Route) {
+        let screen = self.open(route);
+        self.switch_to(screen);
+    }
+
+    /// Returns false when there is nothing to go back to
+    fn back(&mut self) -> bool {
+        let Some(screen) = self.back_stack.pop() else {
+            return false;
+        };
+        let previous = self.switch_to(screen);
+        self.forward_stack.push(previous);
+        true
+    }
+
+    fn forward(&mut self) -> bool {
+        let Some(screen) = self.forward_stack.pop() else {
+            return false;
+        };
+        let previous = self.switch_to(screen);
+        self.back_stack.push(previous);
+        true
+    }
 }
 
 impl Navigation for &App {
@@ -101,18 +319,102 @@ impl Navigation for &App {
             CurrentScreen::Pokedex(pokedex_widget) => {
                 pokedex_widget.handle_navigation_input(direction)
             }
+            CurrentScreen::TypeChart(type_chart) => type_chart.handle_navigation_input(direction),
+            CurrentScreen::Natures(natures) => natures.handle_navigation_input(direction),
+            CurrentScreen::DamageCalc(damage_calc) => damage_calc.handle_navigation_input(direction),
+            CurrentScreen::Team(team) => team.handle_navigation_input(direction),
+            CurrentScreen::Compare(compare) => compare.handle_navigation_input(direction),
+            CurrentScreen::Move(move_screen) => move_screen.handle_navigation_input(direction),
+            CurrentScreen::Ability(ability_screen) => ability_screen.handle_navigation_input(direction),
+            CurrentScreen::Pokemon(pokemon_screen) => pokemon_screen.handle_navigation_input(direction),
         }
     }
 
     fn focus(self) {
         match &self.current_screen {
             CurrentScreen::Pokedex(pokedex_widget) => pokedex_widget.focus(),
+            CurrentScreen::TypeChart(type_chart) => type_chart.focus(),
+            CurrentScreen::Natures(natures) => natures.focus(),
+            CurrentScreen::DamageCalc(damage_calc) => damage_calc.focus(),
+            CurrentScreen::Team(team) => team.focus(),
+            CurrentScreen::Compare(compare) => compare.focus(),
+            CurrentScreen::Move(move_screen) => move_screen.focus(),
+            CurrentScreen::Ability(ability_screen) => ability_screen.focus(),
+            CurrentScreen::Pokemon(pokemon_screen) => pokemon_screen.focus(),
         }
     }
 
     fn unfocus(self) {
         match &self.current_screen {
             CurrentScreen::Pokedex(pokedex_widget) => pokedex_widget.unfocus(),
+            CurrentScreen::TypeChart(type_chart) => type_chart.unfocus(),
+            CurrentScreen::Natures(natures) => natures.unfocus(),
+            CurrentScreen::DamageCalc(damage_calc) => damage_calc.unfocus(),
+            CurrentScreen::Team(team) => team.unfocus(),
+            CurrentScreen::Compare(compare) => compare.unfocus(),
+            CurrentScreen::Move(move_screen) => move_screen.unfocus(),
+            CurrentScreen::Ability(ability_screen) => ability_screen.unfocus(),
+            CurrentScreen::Pokemon(pokemon_screen) => pokemon_screen.unfocus(),
+        }
+    }
+}
+
+impl TextEditing for &App {
+    fn editing(self) -> bool {
+        match &self.current_screen {
+            CurrentScreen::Pokedex(pokedex_widget) => pokedex_widget.editing(),
+            CurrentScreen::TypeChart(type_chart) => type_chart.editing(),
+            CurrentScreen::Natures(natures) => natures.editing(),
+            CurrentScreen::DamageCalc(damage_calc) => damage_calc.editing(),
+            CurrentScreen::Team(team) => team.editing(),
+            CurrentScreen::Compare(compare) => compare.editing(),
+            CurrentScreen::Move(move_screen) => move_screen.editing(),
+            CurrentScreen::Ability(ability_screen) => ability_screen.editing(),
+            CurrentScreen::Pokemon(pokemon_screen) => pokemon_screen.editing(),
+        }
+    }
+
+    fn start_editing(self) -> bool {
+        match &self.current_screen {
+            CurrentScreen::Pokedex(pokedex_widget) => pokedex_widget.start_editing(),
+            CurrentScreen::TypeChart(type_chart) => type_chart.start_editing(),
+            CurrentScreen::Natures(natures) => natures.start_editing(),
+            CurrentScreen::DamageCalc(damage_calc) => damage_calc.start_editing(),
+            CurrentScreen::Team(team) => team.start_editing(),
+            CurrentScreen::Compare(compare) => compare.start_editing(),
+            CurrentScreen::Move(move_screen) => move_screen.start_editing(),
+            CurrentScreen::Ability(ability_screen) => ability_screen.start_editing(),
+            CurrentScreen::Pokemon(pokemon_screen) => pokemon_screen.start_editing(),
+        }
+    }
+
+    fn handle_text_input(self, input: TextInput) -> bool {
+        match &self.current_screen {
+            CurrentScreen::Pokedex(pokedex_widget) => pokedex_widget.handle_text_input(input),
+            CurrentScreen::TypeChart(type_chart) => type_chart.handle_text_input(input),
+            CurrentScreen::Natures(natures) => natures.handle_text_input(input),
+            CurrentScreen::DamageCalc(damage_calc) => damage_calc.handle_text_input(input),
+            CurrentScreen::Team(team) => team.handle_text_input(input),
+            CurrentScreen::Compare(compare) => compare.handle_text_input(input),
+            CurrentScreen::Move(move_screen) => move_screen.handle_text_input(input),
+            CurrentScreen::Ability(ability_screen) => ability_screen.handle_text_input(input),
+            CurrentScreen::Pokemon(pokemon_screen) => pokemon_screen.handle_text_input(input),
+        }
+    }
+}
+
+impl Commands for &App {
+    fn handle_command(self, command: Command) -> bool {
+        match &self.current_screen {
+            CurrentScreen::Pokedex(pokedex_widget) => pokedex_widget.handle_command(command),
+            CurrentScreen::TypeChart(type_chart) => type_chart.handle_command(command),
+            CurrentScreen::Natures(natures) => natures.handle_command(command),
+            CurrentScreen::DamageCalc(damage_calc) => damage_calc.handle_command(command),
+            CurrentScreen::Team(team) => team.handle_command(command),
+            CurrentScreen::Compare(compare) => compare.handle_command(command),
+            CurrentScreen::Move(move_screen) => move_screen.handle_command(command),
+            CurrentScreen::Ability(ability_screen) => ability_screen.handle_command(command),
+            CurrentScreen::Pokemon(pokemon_screen) => pokemon_screen.handle_command(command),
         }
     }
 }
@@ -0,0 +1,32 @@
+use rustemon::model::pokemon::Ability;
+
+use crate::{
+    app::screens::detail::{Detail, DetailScreen},
+    fetch::{FetchError, Priority, Scheduler},
+};
+
+/// Full effect, history and holders of an ability, opened from the abilities list
+pub type AbilityScreen = DetailScreen<Ability>;
+
+impl Detail for Ability {
+    async fn fetch(scheduler: &Scheduler, name: &str) -> Result<Self, FetchError> {
+        scheduler.ability(name, Priority::Visible).await
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn flavor_texts(&self) -> Vec<(&str, &str)> {
+        self.flavor_text_entries
+            .iter()
+            .filter(|entry| entry.language.name == "en")
+            .map(|entry| (entry.version_group.name.as_str(), entry.flavor_text.as_str()))
+            .collect()
+    }
+
+    /// Its holders, hidden ability or not
+    fn pokemon(&self) -> Vec<&str> {
+        self.pokemon.iter().map(|holder| holder.pokemon.name.as_str()).collect()
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    app::widgets::compare::CompareColumn,
+    events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, AppEvent, Event},
+    fetch::Scheduler,
+    pokemon::{effectiveness::TypeChart, PokemonName},
+};
+
+/// More than this and the stat bars get too narrow to read
+pub const MAX_COLUMNS: usize = 4;
+
+/// Pokémon pinned from the pokedex shown next to each other, the first one is what the others are
+/// measured against
+#[derive(Debug, Clone)]
+pub struct CompareScreen {
+    pub sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    pub state: Arc<RwLock<CompareState>>,
+}
+
+impl CompareScreen {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
+        Self {
+            sender,
+            scheduler,
+            state: Default::default(),
+        }
+    }
+
+    /// Adds a column for `name`, replacing the selected one when every column is taken
+    pub fn pin(&self, name: PokemonName) {
+        let mut state = self.state.write().unwrap();
+        if let Some(index) = state.columns.iter().position(|column| column.requested() == name) {
+            state.selected = index;
+            // Pinning it again is how a column that failed gets another go
+            if state.columns[index].failed() {
+                state.columns[index].load(name);
+            }
+        } else if state.columns.len() < MAX_COLUMNS {
+            state.columns.push(CompareColumn::new(self.sender.clone(), self.scheduler.clone(), name));
+            state.selected = state.columns.len() - 1;
+        } else {
+            state.columns[state.selected].load(name);
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn remove_selected(&self) -> bool {
+        let mut state = self.state.write().unwrap();
+        if state.columns.is_empty() {
+            return false;
+        }
+        let selected = state.selected;
+        state.columns.remove(selected).cancel();
+        state.selected = selected.min(state.columns.len().saturating_sub(1));
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CompareState {
+    columns: Vec<CompareColumn>,
+    selected: usize,
+    chart: TypeChart,
+    /// Lines the moves are scrolled by, there are usually far more than fit
+    scroll: u16,
+    /// Name of a Pokémon being typed in
+    editing: Option<String>,
+    focused: bool,
+}
+
+impl CompareState {
+    pub fn columns(&self) -> &[CompareColumn] {
+        &self.columns
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn chart(&self) -> TypeChart {
+        self.chart
+    }
+
+    pub fn scroll(&self) -> u16 {
+        self.scroll
+    }
+
+    pub fn editing(&self) -> Option<&str> {
+        self.editing.as_deref()
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl Navigation for &CompareScreen {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        match direction {
+            NavDirection::Up => state.scroll = state.scroll.saturating_sub(1),
+            NavDirection::Down => state.scroll += 1,
+            NavDirection::BackTab => state.selected = state.selected.saturating_sub(1),
+            NavDirection::Tab => state.selected = usize::min(state.selected + 1, state.columns.len().saturating_sub(1)),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+impl TextEditing for &CompareScreen {
+    fn editing(self) -> bool {
+        self.state.read().unwrap().editing.is_some()
+    }
+
+    fn start_editing(self) -> bool {
+        self.state.write().unwrap().editing = Some(String::new());
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn handle_text_input(self, input: TextInput) -> bool {
+        let mut state = self.state.write().unwrap();
+        let Some(text) = state.editing.as_mut() else {
+            return false;
+        };
+        match input {
+            TextInput::Char(c) => text.push(c),
+            TextInput::Backspace => {
+                text.pop();
+            }
+            TextInput::Submit => {
+                let name = state.editing.take().unwrap_or_default().trim().to_lowercase().replace(' ', "-");
+                if !name.is_empty() {
+                    drop(state);
+                    self.pin(name);
+                    return true;
+                }
+            }
+            TextInput::Cancel => state.editing = None,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
+
+impl Commands for &CompareScreen {
+    fn handle_command(self, command: Command) -> bool {
+        match command {
+            Command::Remove => self.remove_selected(),
+            Command::CycleTypeChart => {
+                let mut state = self.state.write().unwrap();
+                state.chart = state.chart.next();
+                let _ = self.sender.send(Event::App(AppEvent::Redraw));
+                true
+            }
+            _ => false,
+        }
+    }
+}
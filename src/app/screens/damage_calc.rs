@@ -0,0 +1,309 @@
+use std::sync::{Arc, RwLock};
+
+use rustemon::model::{moves::Move, pokemon::Pokemon};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::loading::LoadingState;
+use crate::{
+    events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, AppEvent, Event},
+    fetch::{Priority, Scheduler},
+    pokemon::{
+        damage::{calculate, Category, Combatant, Conditions, Damage, DamageMove},
+        stats::{base_stats, StatSpread},
+        MonStat, MonType,
+    },
+};
+
+/// Rows of the form, in the order they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageRow {
+    Attacker,
+    AttackerLevel,
+    AttackerNature,
+    /// EVs in the stat the move attacks with
+    AttackerEvs,
+    Move,
+    Defender,
+    DefenderLevel,
+    DefenderNature,
+    DefenderHpEvs,
+    /// EVs in the stat the move hits
+    DefenderEvs,
+    Critical,
+    Weather,
+    Burned,
+    /// Type chart, and the generation for the modifiers that changed since Gen 5
+    Generation,
+}
+
+impl DamageRow {
+    pub const ALL: [DamageRow; 14] = [
+        DamageRow::Attacker,
+        DamageRow::AttackerLevel,
+        DamageRow::AttackerNature,
+        DamageRow::AttackerEvs,
+        DamageRow::Move,
+        DamageRow::Defender,
+        DamageRow::DefenderLevel,
+        DamageRow::DefenderNature,
+        DamageRow::DefenderHpEvs,
+        DamageRow::DefenderEvs,
+        DamageRow::Critical,
+        DamageRow::Weather,
+        DamageRow::Burned,
+        DamageRow::Generation,
+    ];
+
+    /// Rows filled in by typing a name
+    fn is_name(self) -> bool {
+        matches!(self, DamageRow::Attacker | DamageRow::Move | DamageRow::Defender)
+    }
+}
+
+/// Damage of one move between two Pokémon, both sides are typed in by name
+#[derive(Debug, Clone)]
+pub struct DamageCalcScreen {
+    pub sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    pub state: Arc<RwLock<DamageCalcState>>,
+}
+
+impl DamageCalcScreen {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
+        Self {
+            sender,
+            scheduler,
+            state: Default::default(),
+        }
+    }
+
+    /// Fills in the attacker and the move, the rest of the form is kept
+    pub fn prefill(&self, attacker: Option<String>, move_: Option<String>) {
+        if let Some(attacker) = attacker {
+            self.load(DamageRow::Attacker, attacker);
+        }
+        if let Some(move_) = move_ {
+            self.load(DamageRow::Move, move_);
+        }
+    }
+
+    fn load(&self, row: DamageRow, name: String) {
+        let name = name.trim().to_lowercase().replace(' ', "-");
+        if name.is_empty() {
+            return;
+        }
+        let mut state = self.state.write().unwrap();
+        match row {
+            DamageRow::Attacker => state.attacker = LoadingState::Loading(name.clone()),
+            DamageRow::Defender => state.defender = LoadingState::Loading(name.clone()),
+            DamageRow::Move => state.move_ = LoadingState::Loading(name.clone()),
+            _ => return,
+        }
+        tokio::spawn(self.clone().fetch(row, name));
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    async fn fetch(self, row: DamageRow, name: String) {
+        if row == DamageRow::Move {
+            let result = self.scheduler.move_(&name, Priority::Visible).await;
+            let mut state = self.state.write().unwrap();
+            if state.move_.is_loading(&name) {
+                state.move_ = result.map_or_else(|err| LoadingState::Error(err.to_string()), |move_| LoadingState::Loaded(Box::new(move_)));
+            }
+        } else {
+            let result = self.scheduler.pokemon(&name, Priority::Visible).await;
+            let mut state = self.state.write().unwrap();
+            let slot = if row == DamageRow::Attacker { &mut state.attacker } else { &mut state.defender };
+            if slot.is_loading(&name) {
+                *slot = result.map_or_else(|err| LoadingState::Error(err.to_string()), |mon| LoadingState::Loaded(Box::new(mon)));
+            }
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn adjust(&self, increase: bool) -> bool {
+        let mut state = self.state.write().unwrap();
+        let category = state.category();
+        let (attack, defense) = category.stats();
+        match state.selected() {
+            DamageRow::AttackerLevel => state.attacker_spread.step_level(increase),
+            DamageRow::AttackerNature => state.attacker_spread.step_nature(increase),
+            DamageRow::AttackerEvs => state.attacker_spread.step_ev(attack, increase),
+            DamageRow::DefenderLevel => state.defender_spread.step_level(increase),
+            DamageRow::DefenderNature => state.defender_spread.step_nature(increase),
+            DamageRow::DefenderHpEvs => state.defender_spread.step_ev(MonStat::HP, increase),
+            DamageRow::DefenderEvs => state.defender_spread.step_ev(defense, increase),
+            DamageRow::Critical => state.conditions.critical = !state.conditions.critical,
+            DamageRow::Burned => state.conditions.burned = !state.conditions.burned,
+            DamageRow::Weather => {
+                let weather = state.conditions.weather;
+                state.conditions.weather = if increase { weather.next() } else { weather.previous() };
+            }
+            DamageRow::Generation => {
+                let chart = state.conditions.chart;
+                state.conditions.chart = if increase { chart.next() } else { chart.previous() };
+            }
+            DamageRow::Attacker | DamageRow::Move | DamageRow::Defender => return false,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DamageCalcState {
+    attacker: LoadingState<Pokemon>,
+    defender: LoadingState<Pokemon>,
+    move_: LoadingState<Move>,
+    attacker_spread: StatSpread,
+    defender_spread: StatSpread,
+    conditions: Conditions,
+    /// Index into [`DamageRow::ALL`]
+    row: usize,
+    /// Text typed into the selected name row
+    editing: Option<String>,
+    focused: bool,
+}
+
+impl DamageCalcState {
+    pub fn attacker(&self) -> &LoadingState<Pokemon> {
+        &self.attacker
+    }
+
+    pub fn defender(&self) -> &LoadingState<Pokemon> {
+        &self.defender
+    }
+
+    pub fn move_(&self) -> &LoadingState<Move> {
+        &self.move_
+    }
+
+    pub fn attacker_spread(&self) -> &StatSpread {
+        &self.attacker_spread
+    }
+
+    pub fn defender_spread(&self) -> &StatSpread {
+        &self.defender_spread
+    }
+
+    pub fn conditions(&self) -> &Conditions {
+        &self.conditions
+    }
+
+    pub fn selected(&self) -> DamageRow {
+        DamageRow::ALL[self.row]
+    }
+
+    pub fn editing(&self) -> Option<&str> {
+        self.editing.as_deref()
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Physical until a special move is loaded
+    pub fn category(&self) -> Category {
+        self.damage_move().map_or(Category::Physical, |move_| move_.category)
+    }
+
+    pub fn damage_move(&self) -> Option<DamageMove> {
+        self.move_.loaded().and_then(DamageMove::from_move)
+    }
+
+    pub fn combatant(mon: &Pokemon, spread: &StatSpread) -> Combatant {
+        Combatant {
+            level: spread.level,
+            types: mon.types.iter().filter_map(|t| t.type_.name.parse::<MonType>().ok()).collect(),
+            stats: spread.stats(base_stats(&mon.stats)),
+        }
+    }
+
+    /// None until both Pokémon and a damaging move are loaded
+    pub fn damage(&self) -> Option<Damage> {
+        let attacker = Self::combatant(self.attacker.loaded()?, &self.attacker_spread);
+        let defender = Self::combatant(self.defender.loaded()?, &self.defender_spread);
+        Some(calculate(&attacker, &defender, &self.damage_move()?, &self.conditions))
+    }
+}
+
+impl Navigation for &DamageCalcScreen {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        if state.editing.is_some() {
+            return false;
+        }
+        match direction {
+            NavDirection::Up => state.row = state.row.saturating_sub(1),
+            NavDirection::Down => state.row = usize::min(state.row + 1, DamageRow::ALL.len() - 1),
+            _ => return false,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+impl TextEditing for &DamageCalcScreen {
+    fn editing(self) -> bool {
+        self.state.read().unwrap().editing.is_some()
+    }
+
+    fn start_editing(self) -> bool {
+        let mut state = self.state.write().unwrap();
+        if !state.selected().is_name() {
+            return false;
+        }
+        state.editing = Some(String::new());
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn handle_text_input(self, input: TextInput) -> bool {
+        let mut state = self.state.write().unwrap();
+        let Some(text) = state.editing.as_mut() else {
+            return false;
+        };
+        match input {
+            TextInput::Char(c) => text.push(c),
+            TextInput::Backspace => {
+                text.pop();
+            }
+            TextInput::Submit => {
+                let (row, name) = (state.selected(), state.editing.take().unwrap_or_default());
+                drop(state);
+                self.load(row, name);
+            }
+            TextInput::Cancel => state.editing = None,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
+
+impl Commands for &DamageCalcScreen {
+    fn handle_command(self, command: Command) -> bool {
+        let selected = self.state.read().unwrap().selected();
+        match command {
+            Command::Select => match selected {
+                row if row.is_name() => self.start_editing(),
+                DamageRow::Critical | DamageRow::Weather | DamageRow::Burned | DamageRow::Generation => {
+                    self.adjust(true)
+                }
+                _ => false,
+            },
+            Command::Increase => self.adjust(true),
+            Command::Decrease => self.adjust(false),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::{fmt, future::Future, sync::{Arc, RwLock}};
+
+use ratatui::widgets::ListState;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::loading::LoadingState;
+use crate::{
+    events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, route::Route, AppEvent, Event},
+    fetch::{FetchError, Scheduler},
+};
+
+/// A PokeAPI resource shown on a screen of its own: a game-by-game flavor text and a list of the
+/// Pokémon it belongs to, enter opens the selected one in place of the screen
+pub trait Detail: fmt::Debug + Clone + Send + Sync + 'static {
+    fn fetch(scheduler: &Scheduler, name: &str) -> impl Future<Output = Result<Self, FetchError>> + Send;
+
+    fn name(&self) -> &str;
+
+    /// English flavor texts as version group and text, oldest game first
+    fn flavor_texts(&self) -> Vec<(&str, &str)>;
+
+    fn pokemon(&self) -> Vec<&str>;
+}
+
+#[derive(Debug, Clone)]
+pub struct DetailScreen<T> {
+    pub sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    pub state: Arc<RwLock<DetailScreenState<T>>>,
+}
+
+impl<T: Detail> DetailScreen<T> {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler, name: String) -> Self {
+        Self {
+            sender,
+            scheduler,
+            state: Arc::new(RwLock::new(DetailScreenState {
+                name: name.clone(),
+                loading_state: LoadingState::Loading(name),
+                flavor_text: 0,
+                pokemon: ListState::default(),
+                focused: false,
+            })),
+        }
+    }
+
+    pub fn run(&self) {
+        let name = self.name();
+        tokio::spawn(self.clone().fetch(name));
+    }
+
+    pub fn name(&self) -> String {
+        self.state.read().unwrap().name.clone()
+    }
+
+    async fn fetch(self, name: String) {
+        let loading_state = match T::fetch(&self.scheduler, &name).await {
+            Ok(resource) => {
+                let mut state = self.state.write().unwrap();
+                // Newest games first, they are the ones people are most likely playing
+                state.flavor_text = resource.flavor_texts().len().saturating_sub(1);
+                state.pokemon.select(Some(0));
+                LoadingState::Loaded(Box::new(resource))
+            }
+            Err(err) => LoadingState::Error(err.to_string()),
+        };
+        self.state.write().unwrap().loading_state = loading_state;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+#[derive(Debug)]
+pub struct DetailScreenState<T> {
+    name: String,
+    loading_state: LoadingState<T>,
+    /// Index into the english flavor text entries
+    flavor_text: usize,
+    pub pokemon: ListState,
+    focused: bool,
+}
+
+impl<T> DetailScreenState<T> {
+    pub fn loading_state(&self) -> &LoadingState<T> {
+        &self.loading_state
+    }
+
+    pub fn flavor_text(&self) -> usize {
+        self.flavor_text
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl<T: Detail> Navigation for &DetailScreen<T> {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        let LoadingState::Loaded(resource) = &state.loading_state else {
+            return false;
+        };
+        let pokemon = resource.pokemon().len();
+        let flavor_texts = resource.flavor_texts().len();
+        match direction {
+            NavDirection::Up => state.pokemon.select_previous(),
+            NavDirection::Down if state.pokemon.selected().is_some_and(|i| i + 1 < pokemon) => {
+                state.pokemon.select_next()
+            }
+            NavDirection::Down => return false,
+            NavDirection::BackTab => state.flavor_text = state.flavor_text.saturating_sub(1),
+            NavDirection::Tab => {
+                state.flavor_text = usize::min(state.flavor_text + 1, flavor_texts.saturating_sub(1))
+            }
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+impl<T> TextEditing for &DetailScreen<T> {
+    fn editing(self) -> bool {
+        false
+    }
+
+    fn start_editing(self) -> bool {
+        false
+    }
+
+    fn handle_text_input(self, _input: TextInput) -> bool {
+        false
+    }
+}
+
+impl<T: Detail> Commands for &DetailScreen<T> {
+    fn handle_command(self, command: Command) -> bool {
+        let state = self.state.read().unwrap();
+        let (Command::Select, LoadingState::Loaded(resource)) = (command, &state.loading_state) else {
+            return false;
+        };
+        match state.pokemon.selected().and_then(|i| resource.pokemon().get(i).copied()) {
+            Some(pokemon) => {
+                // Hopping from one detail screen to the next doesn't pile up history, back still
+                // goes to where the first one was opened from
+                let route = Route::Pokemon(pokemon.to_owned());
+                let _ = self.sender.send(Event::App(AppEvent::Replace(route)));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::config::{CacheConfig, CacheMode, Config};
+
+    #[derive(Debug, Clone)]
+    struct Holders(Vec<&'static str>);
+
+    impl Detail for Holders {
+        async fn fetch(_: &Scheduler, _: &str) -> Result<Self, FetchError> {
+            Err(FetchError::NotCached)
+        }
+
+        fn name(&self) -> &str {
+            "holders"
+        }
+
+        fn flavor_texts(&self) -> Vec<(&str, &str)> {
+            Vec::new()
+        }
+
+        fn pokemon(&self) -> Vec<&str> {
+            self.0.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn selected_pokemon_replaces_the_screen() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        // Offline so nothing is evicted from a real cache
+        let config = Config {
+            cache: CacheConfig { mode: CacheMode::OfflineOnly, ..Default::default() },
+            ..Default::default()
+        };
+        let scheduler = Scheduler::new(sender.clone(), &config).unwrap();
+        let screen = DetailScreen::<Holders>::new(sender, scheduler, "holders".to_owned());
+        {
+            let mut state = screen.state.write().unwrap();
+            state.loading_state = LoadingState::Loaded(Box::new(Holders(vec!["pikachu", "raichu"])));
+            state.pokemon.select(Some(1));
+        }
+        assert!(screen.handle_command(Command::Select));
+        let event = receiver.try_recv().unwrap();
+        assert!(matches!(event, Event::App(AppEvent::Replace(Route::Pokemon(name))) if name == "raichu"));
+    }
+}
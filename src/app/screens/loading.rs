@@ -0,0 +1,37 @@
+use std::fmt;
+
+/// Something a screen fetches by name: nothing picked yet, on its way, there or failed
+#[derive(Debug, Clone, Default)]
+pub enum LoadingState<T> {
+    #[default]
+    Empty,
+    Loading(String),
+    Loaded(Box<T>),
+    Error(String),
+}
+
+impl<T> LoadingState<T> {
+    /// Whether `name` is still the one being loaded, answers to older requests are dropped
+    pub fn is_loading(&self, name: &str) -> bool {
+        matches!(self, LoadingState::Loading(loading) if loading == name)
+    }
+
+    pub fn loaded(&self) -> Option<&T> {
+        match self {
+            LoadingState::Loaded(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Loaded values are left to whatever shows them
+impl<T> fmt::Display for LoadingState<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadingState::Empty => write!(f, "-"),
+            LoadingState::Loading(name) => write!(f, "Loading {0}", name),
+            LoadingState::Loaded(_) => Ok(()),
+            LoadingState::Error(error) => write!(f, "Error {0}", error),
+        }
+    }
+}
@@ -1 +1,11 @@
-pub mod pokedex;
\ No newline at end of file
+pub mod ability_detail;
+pub mod compare;
+pub mod damage_calc;
+pub mod detail;
+pub mod loading;
+pub mod move_detail;
+pub mod natures;
+pub mod pokedex;
+pub mod pokemon;
+pub mod team;
+pub mod type_chart;
@@ -0,0 +1,32 @@
+use rustemon::model::moves::Move;
+
+use crate::{
+    app::screens::detail::{Detail, DetailScreen},
+    fetch::{FetchError, Priority, Scheduler},
+};
+
+/// Everything PokeAPI knows about a single move, opened from the moves list
+pub type MoveScreen = DetailScreen<Move>;
+
+impl Detail for Move {
+    async fn fetch(scheduler: &Scheduler, name: &str) -> Result<Self, FetchError> {
+        scheduler.move_(name, Priority::Visible).await
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn flavor_texts(&self) -> Vec<(&str, &str)> {
+        self.flavor_text_entries
+            .iter()
+            .filter(|entry| entry.language.name == "en")
+            .map(|entry| (entry.version_group.name.as_str(), entry.flavor_text.as_str()))
+            .collect()
+    }
+
+    /// Everything that can learn it
+    fn pokemon(&self) -> Vec<&str> {
+        self.learned_by_pokemon.iter().map(|pokemon| pokemon.name.as_str()).collect()
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, AppEvent, Event}, pokemon::nature::{Nature, AFFECTED}};
+
+/// Every nature laid out by the stat it raises (rows) and the one it lowers (columns)
+#[derive(Debug, Clone)]
+pub struct NaturesScreen {
+    pub sender: UnboundedSender<Event>,
+    pub state: Arc<RwLock<NaturesState>>,
+}
+
+impl NaturesScreen {
+    pub fn new(sender: UnboundedSender<Event>) -> Self {
+        Self {
+            sender,
+            state: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NaturesState {
+    /// Nature under the cursor, its index gives the row and column
+    selected: Nature,
+    focused: bool,
+}
+
+impl NaturesState {
+    pub fn selected(&self) -> Nature {
+        self.selected
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl Navigation for &NaturesScreen {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        let size = AFFECTED.len();
+        let (row, column) = (state.selected as usize / size, state.selected as usize % size);
+        let (row, column) = match direction {
+            NavDirection::Up => (row.saturating_sub(1), column),
+            NavDirection::Down => (usize::min(row + 1, size - 1), column),
+            NavDirection::BackTab => (row, column.saturating_sub(1)),
+            NavDirection::Tab => (row, usize::min(column + 1, size - 1)),
+        };
+        state.selected = Nature::ALL[row * size + column];
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+impl TextEditing for &NaturesScreen {
+    fn editing(self) -> bool {
+        false
+    }
+
+    fn start_editing(self) -> bool {
+        false
+    }
+
+    fn handle_text_input(self, _input: TextInput) -> bool {
+        false
+    }
+}
+
+impl Commands for &NaturesScreen {
+    fn handle_command(self, _command: Command) -> bool {
+        false
+    }
+}
@@ -1,12 +1,21 @@
 use std::{fmt, sync::{Arc, RwLock}};
 
-use rustemon::{error::Error, model::games::Pokedex};
-use tokio::sync::mpsc::UnboundedSender;
+use futures::{future::{join3, join_all}, FutureExt, StreamExt};
+use rustemon::model::games::Pokedex;
+use tokio::{select, sync::mpsc::UnboundedSender};
+use tokio_util::sync::CancellationToken;
 
-use crate::{app::widgets::pokedex::{detail::DetailsWidget, entries::EntriesWidget}, events::{navigation::{NavDirection, Navigation}, AppEvent, Event}, pokemon::get_client};
+use crate::{collection::Collection, fetch::{FetchError, Priority, Scheduler}, pokemon::{fetch_pokemon_or_species, filter::{fetch_index_entry, IndexEntry}, PokemonName}, app::widgets::pokedex::{detail::DetailsWidget, dex_picker::DexPickerWidget, entries::EntriesWidget}, events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, AppEvent, Event}};
 
 
 
+/// Species indexed at once, the scheduler decides how many of their requests actually run
+const INDEX_CONCURRENCY: usize = 8;
+/// Indexed species handed to the list at a time
+const INDEX_BATCH: usize = 32;
+/// Species warmed at once, each one queues its moves, abilities and evolution chain
+const WARM_CONCURRENCY: usize = 4;
+
 #[derive(Debug)]
 pub enum LoadingState
 {
@@ -28,59 +37,225 @@ impl fmt::Display for LoadingState {
 #[derive(Debug, Clone)]
 pub struct PokedexScreen {
     pub sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
     pub entries: EntriesWidget,
     pub detail_view: DetailsWidget,
+    pub dex_picker: DexPickerWidget,
     pub state: Arc<RwLock<PokedexState>>,
 }
 
 impl PokedexScreen {
-    pub fn new(sender: UnboundedSender<Event>) -> Self {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler, collection: Collection) -> Self {
         Self {
             sender: sender.clone(),
             state: Arc::new(RwLock::new(PokedexState::default())),
-            entries: EntriesWidget::new(sender.clone()),
-            detail_view: DetailsWidget::new(sender.clone()),
+            entries: EntriesWidget::new(sender.clone(), collection),
+            detail_view: DetailsWidget::new(sender.clone(), scheduler.clone()),
+            dex_picker: DexPickerWidget::new(sender.clone(), scheduler.clone()),
+            scheduler,
         }
     }
 
     pub fn run(&self) {
-        let this = self.clone();
-        tokio::spawn(this.fetch_dex("national".to_owned()));
+        self.set_dex("national".to_owned());
     }
-    pub fn set_dex(self, name: String) {
+
+    pub fn set_dex(&self, name: String) {
+        self.set_loading_state(LoadingState::Loading(name.clone()));
         let this = self.clone();
         tokio::spawn(this.fetch_dex(name));
     }
 
     async fn fetch_dex(self, name: String) {
-        let rustemon_client = get_client();
-        self.set_loading_state(LoadingState::Loading(name.clone()));
-        match rustemon::games::pokedex::get_by_name(&name, &rustemon_client).await {
+        let result = self.scheduler.pokedex(&name, Priority::Visible).await;
+        // Only the last picked dex gets applied
+        if self.state.read().unwrap().dex_name != name {
+            return;
+        }
+        match result {
             Ok(dex) => self.set_loading_state(LoadingState::Loaded(dex)),
             Err(err) => self.on_err(err),
         }
     }
 
+    /// Indexes whatever the structured filters are missing, unless that is already running. Species
+    /// that failed are tried again with `retry_failed`, which a new dex, a sort or column change and
+    /// submitting the query ask for
+    fn index_missing(&self, retry_failed: bool) {
+        let mut state = self.state.write().unwrap();
+        if state.index_token.is_some() {
+            return;
+        }
+        let names = self.entries.missing_from_index(retry_failed);
+        if names.is_empty() {
+            return;
+        }
+        let token = CancellationToken::new();
+        state.index_token = Some(token.clone());
+        self.entries.set_indexing(true);
+        let this = self.clone();
+        tokio::spawn(async move {
+            select! {
+                _ = token.cancelled() => {}
+                _ = this.build_index(names) => {}
+            }
+        });
+    }
+
+    /// Stops indexing, for when the dex changes under it
+    fn cancel_index(&self) {
+        if let Some(token) = self.state.write().unwrap().index_token.take() {
+            token.cancel();
+        }
+        self.entries.set_indexing(false);
+    }
+
+    /// Results are handed over in batches so the list isn't filtered again for every species
+    async fn build_index(self, names: Vec<PokemonName>) {
+        let scheduler = self.scheduler.clone();
+        let mut batches = futures::stream::iter(names)
+            .map(|name| {
+                let scheduler = scheduler.clone();
+                async move {
+                    let entry = fetch_index_entry(&name, &scheduler, Priority::Background)
+                        .await
+                        .map_err(|err| err.to_string());
+                    (name, entry)
+                }
+            })
+            .buffer_unordered(INDEX_CONCURRENCY)
+            .ready_chunks(INDEX_BATCH);
+        while let Some(batch) = batches.next().await {
+            self.add_to_index(batch);
+        }
+        self.state.write().unwrap().index_token = None;
+        self.entries.set_indexing(false);
+        // The query may have changed to need more while this ran, what failed in this run isn't
+        // tried again straight away
+        self.index_missing(false);
+    }
+
+    /// Starts filling the cache with everything the details show for each species in the dex, or
+    /// stops it when it is already running
+    fn toggle_warm(&self) -> bool {
+        let mut state = self.state.write().unwrap();
+        if let Some(token) = state.warm_token.take() {
+            token.cancel();
+            let _ = self.sender.send(Event::App(AppEvent::Redraw));
+            return true;
+        }
+        let names: Vec<PokemonName> = self
+            .entries
+            .state
+            .read()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.pokemon_species.name.clone())
+            .collect();
+        if names.is_empty() {
+            return false;
+        }
+        let token = CancellationToken::new();
+        state.warm_token = Some(token.clone());
+        state.warm_progress = Some(WarmProgress { total: names.len(), ..Default::default() });
+        let this = self.clone();
+        tokio::spawn(async move {
+            select! {
+                _ = token.cancelled() => {}
+                _ = this.warm(names) => {}
+            }
+        });
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    /// Stops warming and forgets the progress, for when the dex changes under it
+    fn cancel_warm(&self) {
+        let mut state = self.state.write().unwrap();
+        if let Some(token) = state.warm_token.take() {
+            token.cancel();
+        }
+        state.warm_progress = None;
+    }
+
+    /// Warming fetches what the index needs too, so it fills the index as it goes
+    async fn warm(self, names: Vec<PokemonName>) {
+        let scheduler = self.scheduler.clone();
+        let mut batches = futures::stream::iter(names)
+            .map(|name| {
+                let scheduler = scheduler.clone();
+                async move {
+                    let entry = warm_species(&name, &scheduler).await.map_err(|err| err.to_string());
+                    (name, entry)
+                }
+            })
+            .buffer_unordered(WARM_CONCURRENCY)
+            .ready_chunks(INDEX_BATCH);
+        while let Some(batch) = batches.next().await {
+            if let Some(progress) = self.state.write().unwrap().warm_progress.as_mut() {
+                progress.done += batch.len();
+                progress.failed += batch.iter().filter(|(_, entry)| entry.is_err()).count();
+            }
+            self.add_to_index(batch);
+        }
+        self.state.write().unwrap().warm_token = None;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    /// Keeps the details on the selected species when the new entries move it
+    fn add_to_index(&self, batch: Vec<(PokemonName, Result<IndexEntry, String>)>) {
+        let previous = self.entries.get_selectected();
+        let selected = self.entries.add_to_index(batch);
+        if selected != previous && let Some(mon_name) = selected {
+            self.detail_view.set_species(mon_name)
+        }
+    }
+
+    /// Shows the details of whichever species `change` leaves selected, when it hid the old one
+    fn follow_selection(&self, change: impl FnOnce(&EntriesWidget) -> bool) -> bool {
+        let previous = self.entries.get_selectected();
+        let used = change(&self.entries);
+        let selected = self.entries.get_selectected();
+        if used && selected != previous && let Some(mon_name) = selected {
+            self.detail_view.set_species(mon_name)
+        }
+        used
+    }
+
     fn set_loading_state(&self, state: LoadingState) {
         match state
         {
-            LoadingState::Loading(_) =>  self.state.write().unwrap().loading_state = state,
+            LoadingState::Loading(ref name) => {
+                let mut screen_state = self.state.write().unwrap();
+                screen_state.dex_name = name.clone();
+                screen_state.loading_state = state;
+                let _ = self.sender.send(Event::App(AppEvent::Redraw));
+            }
             LoadingState::Loaded(dex) => {
-                match self.entries.set_entries(&dex.pokemon_entries) { //This is Shite
-                    Some(mon_name) => self.detail_view.set_mon(mon_name),
-                    None => {}
+                self.cancel_index();
+                self.cancel_warm();
+                let previous = self.entries.get_selectected();
+                let selected = self.entries.set_entries(&dex.name, &dex.pokemon_entries);
+                if selected != previous && let Some(mon_name) = selected { //This is Shite
+                    self.detail_view.set_species(mon_name)
                 }
                 self.state.write().unwrap().loading_state = LoadingState::Loaded(dex);
+                self.index_missing(true);
                 let _ = self.sender.send(Event::App(AppEvent::Redraw));
-                
+
             },
-            _ => {},
+            LoadingState::Error(_) => {
+                // Offline mode surfaces cache misses here, so they need to be visible
+                self.state.write().unwrap().loading_state = state;
+                let _ = self.sender.send(Event::App(AppEvent::Redraw));
+            }
         }
        
     }
 
 
-    fn on_err(&self, err: Error) {
+    fn on_err(&self, err: FetchError) {
         self.set_loading_state(LoadingState::Error(err.to_string()));
     }
 
@@ -96,23 +271,51 @@ enum PokedexScreenFocus {
 
 #[derive(Debug)]
 pub struct PokedexState {
+    /// The dex being shown, or being loaded to replace it
+    dex_name: String,
     loading_state: LoadingState,
     current_focus: PokedexScreenFocus,
     focused: bool,
+    /// Set while the structured filters' index is being built
+    index_token: Option<CancellationToken>,
+    /// Set while the cache is being warmed
+    warm_token: Option<CancellationToken>,
+    /// How far warming got for the current dex, kept once it is done
+    warm_progress: Option<WarmProgress>,
 }
 
 impl PokedexState {
     pub fn loading_state(&self) -> &LoadingState {
         &self.loading_state
     }
+
+    pub fn warm_progress(&self) -> Option<WarmProgress> {
+        self.warm_progress
+    }
+
+    pub fn warming(&self) -> bool {
+        self.warm_token.is_some()
+    }
+}
+
+/// Species of the dex warmed so far, failed ones count as done
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WarmProgress {
+    pub done: usize,
+    pub failed: usize,
+    pub total: usize,
 }
 
 impl Default for PokedexState {
     fn default() -> Self {
         Self {
+            dex_name: String::from("national"),
             loading_state: LoadingState::Loading(String::from("national")),
             current_focus: Default::default(),
-            focused: Default::default()
+            focused: Default::default(),
+            index_token: None,
+            warm_token: None,
+            warm_progress: None,
         }
     }
 }
@@ -121,15 +324,15 @@ impl Navigation for &PokedexScreen
 {
     fn handle_navigation_input(self, direction: NavDirection)-> bool
     {
+        if self.dex_picker.is_open() {
+            return self.dex_picker.handle_navigation_input(direction);
+        }
         let mut state = self.state.write().unwrap();
         let used = match state.current_focus {
             PokedexScreenFocus::List => {
                 let used = self.entries.handle_navigation_input(direction);
-                if used {
-                    match self.entries.get_selectected() {
-                        Some(mon_name) => self.detail_view.set_mon(mon_name),
-                        None => {}
-                    }
+                if used && let Some(mon_name) = self.entries.get_selectected() {
+                    self.detail_view.set_species(mon_name)
                 }
 
                 used
@@ -161,11 +364,13 @@ impl Navigation for &PokedexScreen
 
     }
     
+    /// Children keep their focus while the screen is hidden, so coming back restores it
     fn focus(self) {
         let mut state = self.state.write().unwrap();
         state.focused= true;
-        state.current_focus = PokedexScreenFocus::List;
-        self.entries.focus();
+        if let PokedexScreenFocus::List = state.current_focus {
+            self.entries.focus();
+        }
         let _ = self.sender.send(Event::App(AppEvent::Redraw));
 
     }
@@ -176,4 +381,95 @@ impl Navigation for &PokedexScreen
 
     }
     
-}
\ No newline at end of file
+}
+
+impl TextEditing for &PokedexScreen
+{
+    fn editing(self) -> bool {
+        self.entries.editing()
+    }
+
+    fn start_editing(self) -> bool {
+        if self.dex_picker.is_open() {
+            return false;
+        }
+        let mut state = self.state.write().unwrap();
+        if let PokedexScreenFocus::Details = state.current_focus {
+            self.detail_view.unfocus();
+            state.current_focus = PokedexScreenFocus::List;
+            self.entries.focus();
+        }
+        self.entries.start_editing()
+    }
+
+    fn handle_text_input(self, input: TextInput) -> bool {
+        let submitted = matches!(input, TextInput::Submit);
+        let previous = self.entries.get_selectected();
+        let used = self.entries.handle_text_input(input);
+        let selected = self.entries.get_selectected();
+        if used && selected != previous && let Some(mon_name) = selected {
+            self.detail_view.set_species(mon_name)
+        }
+        // Only submitting the query asks for failed species again, not every key typed into it
+        self.index_missing(submitted);
+        used
+    }
+}
+
+impl Commands for &PokedexScreen
+{
+    fn handle_command(self, command: Command) -> bool {
+        match (command, self.dex_picker.is_open()) {
+            (Command::PickDex, false) => {
+                let current = self.state.read().unwrap().dex_name.clone();
+                self.dex_picker.open(&current);
+                true
+            }
+            (Command::Select, true) => {
+                if let Some(name) = self.dex_picker.get_selected() {
+                    self.dex_picker.close();
+                    self.set_dex(name);
+                }
+                true
+            }
+            (Command::Cancel, true) => {
+                self.dex_picker.close();
+                true
+            }
+            (_, true) => true,
+            (Command::Mark(mark), false) => self.follow_selection(|entries| entries.toggle_mark(mark)),
+            (Command::ToggleMissingOnly, false) => self.follow_selection(EntriesWidget::toggle_missing_only),
+            // Stat columns and sorting need the index, the selected species stays so the details do too
+            (Command::CycleSort, false) => {
+                self.entries.cycle_sort();
+                self.index_missing(true);
+                true
+            }
+            (Command::ReverseSort, false) => {
+                self.entries.reverse_sort();
+                true
+            }
+            (Command::ToggleColumns, false) => {
+                self.entries.toggle_columns();
+                self.index_missing(true);
+                true
+            }
+            (Command::WarmCache, false) => self.toggle_warm(),
+            (command, false) => self.detail_view.handle_command(command),
+        }
+    }
+}
+
+/// Fetches everything the details show for a species. Only the species and its default form have
+/// to load for the index entry, the rest only fills the cache so its errors don't matter
+async fn warm_species(name: &str, scheduler: &Scheduler) -> Result<IndexEntry, FetchError> {
+    let species = scheduler.species(name, Priority::Warm).await?;
+    let mon = fetch_pokemon_or_species(name, scheduler, Priority::Warm).await?;
+    join3(
+        join_all(mon.moves.iter().map(|m| scheduler.follow(&m.move_, Priority::Warm).map(drop))),
+        join_all(mon.abilities.iter().filter_map(|a| a.ability.as_ref()).map(|a| scheduler.follow(a, Priority::Warm).map(drop))),
+        join_all(species.evolution_chain.iter().map(|chain| scheduler.follow(chain, Priority::Warm).map(drop))),
+    )
+    .await;
+    Ok(IndexEntry::new(&species, &mon))
+}
@@ -0,0 +1,62 @@
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{fetch::Scheduler, app::widgets::pokedex::detail::DetailsWidget, events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, Event}, pokemon::PokemonName};
+
+/// The details panel on its own, for Pokémon opened from another screen
+#[derive(Debug, Clone)]
+pub struct PokemonScreen {
+    name: PokemonName,
+    pub detail_view: DetailsWidget,
+}
+
+impl PokemonScreen {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler, name: PokemonName) -> Self {
+        Self {
+            name,
+            detail_view: DetailsWidget::new(sender, scheduler),
+        }
+    }
+
+    pub fn run(&self) {
+        self.detail_view.set_mon(self.name.clone());
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Navigation for &PokemonScreen {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        self.detail_view.handle_navigation_input(direction)
+    }
+
+    /// Only the first focus resets the panel, coming back keeps the selected ability or move
+    fn focus(self) {
+        if !self.detail_view.state.read().unwrap().focused() {
+            self.detail_view.focus();
+        }
+    }
+
+    fn unfocus(self) {}
+}
+
+impl TextEditing for &PokemonScreen {
+    fn editing(self) -> bool {
+        false
+    }
+
+    fn start_editing(self) -> bool {
+        false
+    }
+
+    fn handle_text_input(self, _input: TextInput) -> bool {
+        false
+    }
+}
+
+impl Commands for &PokemonScreen {
+    fn handle_command(self, command: Command) -> bool {
+        self.detail_view.handle_command(command)
+    }
+}
@@ -0,0 +1,489 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use rustemon::model::{moves::Move, pokemon::Pokemon};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, AppEvent, Event, TeamAddition},
+    fetch::{Priority, Scheduler},
+    pokemon::{
+        effectiveness::TypeChart,
+        showdown::{self, ShowdownSet},
+        team::{add_move, coverage, weaknesses, TeamMatchup, TeamMember, MAX_MOVES, TEAM_SIZE},
+        MonStat, MonType,
+    },
+};
+
+use super::loading::LoadingState;
+
+/// Rows of the selected member's form, in the order they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeamRow {
+    Pokemon,
+    Item,
+    Ability,
+    Level,
+    Nature,
+    Ev(MonStat),
+    /// Index into the member's moves
+    Move(usize),
+}
+
+impl TeamRow {
+    pub fn all() -> Vec<TeamRow> {
+        let mut rows = vec![TeamRow::Pokemon, TeamRow::Item, TeamRow::Ability, TeamRow::Level, TeamRow::Nature];
+        rows.extend(MonStat::ALL.map(TeamRow::Ev));
+        rows.extend((0..MAX_MOVES).map(TeamRow::Move));
+        rows
+    }
+}
+
+/// What the text box at the bottom is being used for
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TeamPrompt {
+    #[default]
+    Item,
+    /// Path of a Showdown paste to load
+    Import,
+    /// Path to write the team to
+    Export,
+}
+
+/// Used when no path has been typed yet
+const DEFAULT_PATH: &str = "team.txt";
+
+/// Six team slots filled from the pokedex, with the team's coverage and shared weaknesses
+#[derive(Debug, Clone)]
+pub struct TeamScreen {
+    pub sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    pub state: Arc<RwLock<TeamState>>,
+}
+
+impl TeamScreen {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
+        Self {
+            sender,
+            scheduler,
+            state: Default::default(),
+        }
+    }
+
+    /// Pokémon go to the first empty slot, or replace the selected one when the team is full.
+    /// Abilities and moves go to the selected slot if its Pokémon can have them
+    pub fn add(&self, addition: TeamAddition) {
+        let mut state = self.state.write().unwrap();
+        let slot = state.slot;
+        let message = match addition {
+            TeamAddition::Pokemon(name) => {
+                let slot = state.members.iter().position(Option::is_none).unwrap_or(slot);
+                state.slot = slot;
+                drop(state);
+                self.set_member(slot, TeamMember::new(name.clone()));
+                state = self.state.write().unwrap();
+                format!("Added {name} to slot {}", slot + 1)
+            }
+            TeamAddition::Ability(ability) => match (&state.members[slot], state.pokemon[slot].loaded()) {
+                (Some(_), Some(mon)) if mon.abilities.iter().flat_map(|a| &a.ability).any(|a| a.name == ability) => {
+                    let message = format!("{} now has {ability}", mon.name);
+                    state.members[slot].as_mut().unwrap().ability = Some(ability);
+                    message
+                }
+                (Some(member), Some(_)) => format!("{} can't have {ability}", member.pokemon),
+                (Some(member), None) => format!("{} is still loading", member.pokemon),
+                (None, _) => format!("Slot {} is empty", slot + 1),
+            },
+            TeamAddition::Move(move_) => {
+                let learnable: Option<Vec<String>> = state.pokemon[slot]
+                    .loaded()
+                    .map(|mon| mon.moves.iter().map(|m| m.move_.name.clone()).collect());
+                match (state.members[slot].as_mut(), learnable) {
+                    (Some(member), Some(learnable)) => {
+                        let learnable: Vec<&str> = learnable.iter().map(String::as_str).collect();
+                        match add_move(member, &move_, &learnable) {
+                            Ok(()) => {
+                                let message = format!("{} learned {move_}", member.pokemon);
+                                drop(state);
+                                self.load_move(move_);
+                                state = self.state.write().unwrap();
+                                message
+                            }
+                            Err(err) => err.to_string(),
+                        }
+                    }
+                    (Some(member), None) => format!("{} is still loading", member.pokemon),
+                    (None, _) => format!("Slot {} is empty", slot + 1),
+                }
+            }
+        };
+        state.messages = vec![message];
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    /// Replaces a whole slot and loads what it needs
+    pub fn set_member(&self, slot: usize, member: TeamMember) {
+        let mut state = self.state.write().unwrap();
+        let (name, moves) = (member.pokemon.clone(), member.moves.clone());
+        state.members[slot] = Some(member);
+        state.pokemon[slot] = LoadingState::Loading(name.clone());
+        drop(state);
+        tokio::spawn(self.clone().fetch_pokemon(slot, name));
+        for move_ in moves {
+            self.load_move(move_);
+        }
+    }
+
+    /// Parses a Showdown paste, then checks its names against PokeAPI before replacing the team
+    pub fn import_text(&self, text: &str) {
+        let mut state = self.state.write().unwrap();
+        match showdown::parse(text) {
+            Ok(sets) if sets.is_empty() => state.messages = vec!["Nothing to import".to_owned()],
+            Ok(sets) => {
+                state.messages = vec![format!("Checking {} Pokémon", sets.len())];
+                tokio::spawn(self.clone().fetch_import(sets));
+            }
+            Err(errors) => state.messages = errors.iter().map(ToString::to_string).collect(),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    async fn fetch_import(self, sets: Vec<ShowdownSet>) {
+        let result = showdown::validate(&self.scheduler, sets).await;
+        let mut state = self.state.write().unwrap();
+        match result {
+            Ok(members) => {
+                state.messages = vec![format!("Imported {} Pokémon", members.len())];
+                state.members = Default::default();
+                state.pokemon = Default::default();
+                state.slot = 0;
+                drop(state);
+                for (slot, member) in members.into_iter().enumerate() {
+                    self.set_member(slot, member);
+                }
+            }
+            Err(errors) => state.messages = errors.iter().map(ToString::to_string).collect(),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn import_file(&self, path: &str) {
+        match std::fs::read_to_string(expand_home(path)) {
+            Ok(text) => self.import_text(&text),
+            Err(err) => self.state.write().unwrap().messages = vec![format!("Can't read {path}: {err}")],
+        }
+    }
+
+    /// Writes the team to `path` and copies it to the clipboard
+    fn export(&self, path: &str) {
+        let mut state = self.state.write().unwrap();
+        let members: Vec<&TeamMember> = state.members.iter().flatten().collect();
+        if members.is_empty() {
+            state.messages = vec!["The team is empty".to_owned()];
+            return;
+        }
+        let (count, text) = (members.len(), showdown::export(members));
+        state.messages = match std::fs::write(expand_home(path), &text) {
+            Ok(()) => vec![format!("Exported {count} Pokémon to {path}")],
+            Err(err) => vec![format!("Can't write {path}: {err}")],
+        };
+        if let Err(err) = copy_to_clipboard(&text) {
+            state.messages.push(format!("Can't copy to the clipboard: {err}"));
+        }
+    }
+
+    fn start_prompt(&self, prompt: TeamPrompt) -> bool {
+        let mut state = self.state.write().unwrap();
+        state.prompt = prompt;
+        state.editing = Some(state.path.clone().unwrap_or_else(|| DEFAULT_PATH.to_owned()));
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    async fn fetch_pokemon(self, slot: usize, name: String) {
+        let result = self.scheduler.pokemon(&name, Priority::Visible).await;
+        let mut state = self.state.write().unwrap();
+        if !state.pokemon[slot].is_loading(&name) {
+            return;
+        }
+        state.pokemon[slot] = match result {
+            Ok(mon) => {
+                // Start from the first ability so the slot is always complete
+                if let Some(member) = state.members[slot].as_mut()
+                    && member.ability.is_none()
+                {
+                    member.ability = mon.abilities.iter().find_map(|a| a.ability.as_ref()).map(|a| a.name.clone());
+                }
+                LoadingState::Loaded(Box::new(mon))
+            }
+            Err(err) => LoadingState::Error(err.to_string()),
+        };
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    /// Moves are only needed for their type, so they are shared between slots
+    fn load_move(&self, name: String) {
+        let mut state = self.state.write().unwrap();
+        if state.moves.contains_key(&name) {
+            return;
+        }
+        state.moves.insert(name.clone(), LoadingState::Loading(name.clone()));
+        tokio::spawn(self.clone().fetch_move(name));
+    }
+
+    async fn fetch_move(self, name: String) {
+        let result = self.scheduler.move_(&name, Priority::Visible).await;
+        let slot = result.map_or_else(|err| LoadingState::Error(err.to_string()), |move_| LoadingState::Loaded(Box::new(move_)));
+        self.state.write().unwrap().moves.insert(name, slot);
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn adjust(&self, increase: bool) -> bool {
+        let mut state = self.state.write().unwrap();
+        let (slot, row) = (state.slot, state.selected());
+        let abilities: Vec<String> = state.pokemon[slot]
+            .loaded()
+            .map(|mon| mon.abilities.iter().flat_map(|a| &a.ability).map(|a| a.name.clone()).collect())
+            .unwrap_or_default();
+        let Some(member) = state.members[slot].as_mut() else {
+            return false;
+        };
+        match row {
+            TeamRow::Ability if !abilities.is_empty() => {
+                let current = abilities.iter().position(|a| Some(a) == member.ability.as_ref()).unwrap_or(0);
+                let next = if increase { current + 1 } else { current + abilities.len() - 1 };
+                member.ability = Some(abilities[next % abilities.len()].clone());
+            }
+            TeamRow::Level => member.spread.step_level(increase),
+            TeamRow::Nature => member.spread.step_nature(increase),
+            TeamRow::Ev(stat) => member.spread.step_ev(stat, increase),
+            _ => return false,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TeamState {
+    members: [Option<TeamMember>; TEAM_SIZE],
+    /// PokeAPI data for each slot, used for legality checks, types and abilities
+    pokemon: [LoadingState<Pokemon>; TEAM_SIZE],
+    moves: HashMap<String, LoadingState<Move>>,
+    slot: usize,
+    /// Index into [`TeamRow::all`]
+    row: usize,
+    /// Text being typed in, what it is for is in `prompt`
+    editing: Option<String>,
+    prompt: TeamPrompt,
+    /// Last path imported from or exported to
+    path: Option<String>,
+    /// Outcome of the last addition, import or export
+    messages: Vec<String>,
+    focused: bool,
+}
+
+impl TeamState {
+    pub fn members(&self) -> &[Option<TeamMember>; TEAM_SIZE] {
+        &self.members
+    }
+
+    pub fn pokemon(&self, slot: usize) -> &LoadingState<Pokemon> {
+        &self.pokemon[slot]
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    pub fn selected(&self) -> TeamRow {
+        TeamRow::all()[self.row]
+    }
+
+    pub fn editing(&self) -> Option<&str> {
+        self.editing.as_deref()
+    }
+
+    pub fn prompt(&self) -> TeamPrompt {
+        self.prompt
+    }
+
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Type of a damaging move, None for status moves and moves still loading
+    pub fn move_type(&self, name: &str) -> Option<MonType> {
+        let move_ = self.moves.get(name)?.loaded()?;
+        if move_.damage_class.name == "status" {
+            return None;
+        }
+        move_.type_.name.parse().ok()
+    }
+
+    fn member_types(&self) -> Vec<Vec<MonType>> {
+        self.pokemon
+            .iter()
+            .filter_map(LoadingState::loaded)
+            .map(|mon| mon.types.iter().filter_map(|t| t.type_.name.parse().ok()).collect())
+            .collect()
+    }
+
+    /// Types the team's damaging moves hit super effectively
+    pub fn coverage(&self) -> Vec<MonType> {
+        let move_types: Vec<MonType> = self
+            .members
+            .iter()
+            .flatten()
+            .flat_map(|member| member.moves.iter())
+            .filter_map(|move_| self.move_type(move_))
+            .collect();
+        coverage(&move_types, TypeChart::default())
+    }
+
+    pub fn weaknesses(&self) -> Vec<TeamMatchup> {
+        weaknesses(&self.member_types(), TypeChart::default())
+    }
+}
+
+impl Navigation for &TeamScreen {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        if state.editing.is_some() {
+            return false;
+        }
+        match direction {
+            NavDirection::Up => state.row = state.row.saturating_sub(1),
+            NavDirection::Down => state.row = usize::min(state.row + 1, TeamRow::all().len() - 1),
+            NavDirection::BackTab => state.slot = state.slot.saturating_sub(1),
+            NavDirection::Tab => state.slot = usize::min(state.slot + 1, TEAM_SIZE - 1),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+impl TextEditing for &TeamScreen {
+    fn editing(self) -> bool {
+        self.state.read().unwrap().editing.is_some()
+    }
+
+    fn start_editing(self) -> bool {
+        let mut state = self.state.write().unwrap();
+        let slot = state.slot;
+        let (TeamRow::Item, Some(member)) = (state.selected(), &state.members[slot]) else {
+            return false;
+        };
+        state.editing = Some(member.item.clone().unwrap_or_default().replace('-', " "));
+        state.prompt = TeamPrompt::Item;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn handle_text_input(self, input: TextInput) -> bool {
+        let mut state = self.state.write().unwrap();
+        let Some(text) = state.editing.as_mut() else {
+            return false;
+        };
+        match input {
+            TextInput::Char(c) => text.push(c),
+            TextInput::Backspace => {
+                text.pop();
+            }
+            TextInput::Submit => {
+                let text = state.editing.take().unwrap_or_default().trim().to_owned();
+                match state.prompt {
+                    TeamPrompt::Item => {
+                        let item = text.to_lowercase().replace(' ', "-");
+                        let slot = state.slot;
+                        if let Some(member) = state.members[slot].as_mut() {
+                            member.item = (!item.is_empty()).then_some(item);
+                        }
+                    }
+                    _ if text.is_empty() => {}
+                    prompt => {
+                        state.path = Some(text.clone());
+                        drop(state);
+                        match prompt {
+                            TeamPrompt::Import => self.import_file(&text),
+                            _ => self.export(&text),
+                        }
+                        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+                        return true;
+                    }
+                }
+            }
+            TextInput::Cancel => state.editing = None,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
+
+impl Commands for &TeamScreen {
+    fn handle_command(self, command: Command) -> bool {
+        let row = self.state.read().unwrap().selected();
+        match (command, row) {
+            (Command::Select, TeamRow::Item) => self.start_editing(),
+            (Command::Remove, TeamRow::Pokemon) => {
+                let mut state = self.state.write().unwrap();
+                let slot = state.slot;
+                state.members[slot] = None;
+                state.pokemon[slot] = LoadingState::Empty;
+                let _ = self.sender.send(Event::App(AppEvent::Redraw));
+                true
+            }
+            (Command::Remove, TeamRow::Move(i)) => {
+                let mut state = self.state.write().unwrap();
+                let slot = state.slot;
+                match state.members[slot].as_mut() {
+                    Some(member) if i < member.moves.len() => {
+                        member.moves.remove(i);
+                        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            (Command::Import, _) => self.start_prompt(TeamPrompt::Import),
+            (Command::Export, _) => self.start_prompt(TeamPrompt::Export),
+            (Command::Increase, _) => self.adjust(true),
+            (Command::Decrease, _) => self.adjust(false),
+            _ => false,
+        }
+    }
+}
+
+/// Relative paths are taken from the working directory, `~/` from the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Copies through the terminal with OSC 52, which also works over SSH where there is no clipboard to link against
+fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text))?;
+    stdout.flush()
+}
@@ -0,0 +1,140 @@
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{events::{command::{Command, Commands}, input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, AppEvent, Event}, pokemon::{effectiveness::TypeChart, MonType}};
+
+/// Attacking vs defending type matrix, rows are the attacking type and columns the defending one
+#[derive(Debug, Clone)]
+pub struct TypeChartScreen {
+    pub sender: UnboundedSender<Event>,
+    pub state: Arc<RwLock<TypeChartState>>,
+}
+
+impl TypeChartScreen {
+    pub fn new(sender: UnboundedSender<Event>) -> Self {
+        Self {
+            sender,
+            state: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TypeChartState {
+    chart: TypeChart,
+    /// Row of the cursor, an index into [`TypeChartState::types`]
+    attacking: usize,
+    /// Column of the cursor, an index into [`TypeChartState::types`]
+    defending: usize,
+    /// First type of the defender in dual-type mode, the columns then give the second one
+    pinned: Option<MonType>,
+    focused: bool,
+}
+
+impl TypeChartState {
+    pub fn chart(&self) -> TypeChart {
+        self.chart
+    }
+
+    pub fn types(&self) -> Vec<MonType> {
+        self.chart.types().collect()
+    }
+
+    pub fn attacking(&self) -> usize {
+        self.attacking
+    }
+
+    pub fn defending(&self) -> usize {
+        self.defending
+    }
+
+    pub fn pinned(&self) -> Option<MonType> {
+        self.pinned
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Types of the defender shown in a column, a single type unless one is pinned
+    pub fn defender(&self, column: MonType) -> Vec<MonType> {
+        match self.pinned {
+            Some(pinned) if pinned != column => vec![pinned, column],
+            _ => vec![column],
+        }
+    }
+
+    fn set_chart(&mut self, chart: TypeChart) {
+        self.chart = chart;
+        let count = self.chart.types().count();
+        self.attacking = usize::min(self.attacking, count - 1);
+        self.defending = usize::min(self.defending, count - 1);
+        if self.pinned.is_some_and(|pinned| !pinned.exists_in(chart)) {
+            self.pinned = None;
+        }
+    }
+}
+
+impl Navigation for &TypeChartScreen {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        let last = state.chart.types().count() - 1;
+        match direction {
+            NavDirection::Up => state.attacking = state.attacking.saturating_sub(1),
+            NavDirection::Down => state.attacking = usize::min(state.attacking + 1, last),
+            NavDirection::BackTab => state.defending = state.defending.saturating_sub(1),
+            NavDirection::Tab => state.defending = usize::min(state.defending + 1, last),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+impl TextEditing for &TypeChartScreen {
+    fn editing(self) -> bool {
+        false
+    }
+
+    fn start_editing(self) -> bool {
+        false
+    }
+
+    fn handle_text_input(self, _input: TextInput) -> bool {
+        false
+    }
+}
+
+impl Commands for &TypeChartScreen {
+    fn handle_command(self, command: Command) -> bool {
+        let mut state = self.state.write().unwrap();
+        match command {
+            Command::CycleTypeChart => {
+                let chart = state.chart.next();
+                state.set_chart(chart);
+            }
+            // Pins the column's type as the first defending type, or unpins it
+            Command::Select => {
+                let column = state.types()[state.defending];
+                state.pinned = match state.pinned {
+                    Some(pinned) if pinned == column => None,
+                    _ => Some(column),
+                };
+            }
+            Command::Cancel if state.pinned.is_some() => state.pinned = None,
+            _ => return false,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
@@ -0,0 +1,96 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, Paragraph, StatefulWidget, Widget, Wrap},
+};
+
+use crate::app::{
+    screens::{ability_detail::AbilityScreen, loading::LoadingState},
+    ui::detail::{render_flavor_text, screen_block},
+};
+
+impl Widget for &AbilityScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = self.state.write().unwrap();
+        let block = screen_block(&state, "enter to open a holder, h/l to change game, backspace to go back");
+        let LoadingState::Loaded(ability) = state.loading_state().clone() else {
+            block.render(area, buf);
+            return;
+        };
+        let [left, right] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(28)]).areas(block.inner(area));
+        let [header, _, effect, history, flavor_text] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+        ])
+        .areas(left);
+
+        let name = ability
+            .names
+            .iter()
+            .find(|name| name.language.name == "en")
+            .map(|name| name.name.clone())
+            .unwrap_or_else(|| ability.name.clone());
+        Line::from(vec![
+            name.to_uppercase().bold(),
+            Span::from(format!(
+                "  introduced in generation {}",
+                ability.generation.name.trim_start_matches("generation-").to_uppercase()
+            )),
+        ])
+        .render(header, buf);
+
+        let effect_text = ability
+            .effect_entries
+            .iter()
+            .find(|entry| entry.language.name == "en")
+            .map(|entry| entry.effect.clone())
+            .unwrap_or_default();
+        Paragraph::new(effect_text)
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title("Effect"))
+            .render(effect, buf);
+
+        // PokeAPI lists the effect each version group had before it was changed
+        let changes: Vec<Line> = ability
+            .effect_changes
+            .iter()
+            .filter_map(|change| {
+                let effect = change.effect_entries.iter().find(|entry| entry.language.name == "en")?;
+                Some(Line::from(vec![
+                    Span::from(format!("Before {}: ", change.version_group.name)).bold(),
+                    Span::from(effect.effect.clone()),
+                ]))
+            })
+            .collect();
+        let history_text = if changes.is_empty() {
+            vec![Line::from("Unchanged since it was introduced")]
+        } else {
+            changes
+        };
+        Paragraph::new(history_text)
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title("Version history"))
+            .render(history, buf);
+
+        render_flavor_text(&*ability, state.flavor_text(), flavor_text, buf);
+
+        let holders = List::new(ability.pokemon.iter().map(|holder| {
+            let name = holder.pokemon.name.to_uppercase();
+            if holder.is_hidden {
+                ListItem::new(format!("{name:<18}Hidden"))
+            } else {
+                ListItem::new(name)
+            }
+        }))
+        .block(Block::bordered().title(format!("Holders ({0})", ability.pokemon.len())))
+        .highlight_style(Style::new().black().on_blue());
+        StatefulWidget::render(holders, right, buf, &mut state.pokemon);
+        block.render(area, buf);
+    }
+}
@@ -1,6 +1,15 @@
-use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, text::Line, widgets::Widget};
+use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, style::{Color, Style}, text::Line, widgets::Widget};
 
-use crate::app::{App, CurrentScreen};
+use crate::{app::{App, CurrentScreen}, fetch::RetryStatus};
+
+/// Shown while PokeAPI is struggling, so slow loading doesn't look like a hang
+fn retry_status(status: &RetryStatus) -> Line<'static> {
+    let mut text = format!("retrying {} ({}/{})", status.path, status.attempt, status.retries);
+    if status.waiting > 1 {
+        text.push_str(&format!(" +{} more", status.waiting - 1));
+    }
+    Line::styled(text, Style::new().fg(Color::Yellow)).right_aligned()
+}
 
 impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
@@ -8,8 +17,19 @@ impl Widget for &App {
         let [title_area, body_area] = vertical.areas(area);
         let title = Line::from("RustyDex").centered();
         title.render(title_area, buf);
+        if let Some(status) = self.scheduler.retrying() {
+            retry_status(&status).render(title_area, buf);
+        }
         match &self.current_screen {
             CurrentScreen::Pokedex(widget) => widget.clone().render(body_area, buf),
+            CurrentScreen::TypeChart(widget) => widget.render(body_area, buf),
+            CurrentScreen::Natures(widget) => widget.render(body_area, buf),
+            CurrentScreen::DamageCalc(widget) => widget.render(body_area, buf),
+            CurrentScreen::Team(widget) => widget.render(body_area, buf),
+            CurrentScreen::Compare(widget) => widget.render(body_area, buf),
+            CurrentScreen::Move(widget) => widget.render(body_area, buf),
+            CurrentScreen::Ability(widget) => widget.render(body_area, buf),
+            CurrentScreen::Pokemon(widget) => widget.render(body_area, buf),
         }
     }
 }
\ No newline at end of file
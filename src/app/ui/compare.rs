@@ -0,0 +1,201 @@
+use std::str::FromStr;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Gauge, Paragraph, Row, Table, Widget, Wrap},
+};
+use rustemon::model::pokemon::Pokemon;
+
+use crate::{
+    app::{screens::compare::CompareScreen, ui::pokedex::detail::type_span, widgets::pokedex::detail::LoadingState},
+    pokemon::{
+        compare::shared_and_unique,
+        effectiveness::{format_multiplier, types_under, Matchups, TypeChart},
+        stats::base_stats,
+        MonStat, MonType,
+    },
+};
+
+/// Name, types, six stats and the total, inside a border
+const COLUMN_HEIGHT: u16 = 11;
+
+impl Widget for &CompareScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.read().unwrap();
+        let block = Block::bordered()
+            .title("Compare")
+            .title_bottom(match state.editing() {
+                Some(_) => "enter to add, esc to cancel",
+                None => "x in the pokedex pins, / to add by name, h/l to pick, r to remove, g for generation, j/k to scroll",
+            })
+            .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() });
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [columns_area, prompt_area, details_area] =
+            Layout::vertical([Constraint::Length(COLUMN_HEIGHT), Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+        if let Some(text) = state.editing() {
+            Line::from(format!("Add: {text}_")).black().on_blue().render(prompt_area, buf);
+        }
+        if state.columns().is_empty() {
+            Paragraph::new("Nothing pinned, press x on a Pokémon in the pokedex or / to type a name")
+                .wrap(Wrap { trim: true })
+                .render(columns_area, buf);
+            return;
+        }
+
+        let guards: Vec<_> = state.columns().iter().map(|column| column.state.read().unwrap()).collect();
+        let areas = Layout::horizontal(vec![Constraint::Fill(1); guards.len()]).split(columns_area);
+        // Everything is measured against the first column
+        let pinned = match &*guards[0] {
+            LoadingState::Loaded(mon) => Some(base_stats(&mon.stats)),
+            _ => None,
+        };
+        for (i, (guard, area)) in guards.iter().zip(areas.iter()).enumerate() {
+            let selected = i == state.selected() && state.focused();
+            render_column(guard, pinned.filter(|_| i > 0), selected, *area, buf);
+        }
+
+        let loaded: Vec<&Pokemon> = guards
+            .iter()
+            .filter_map(|guard| match &**guard {
+                LoadingState::Loaded(mon) => Some(mon.as_ref()),
+                _ => None,
+            })
+            .collect();
+        if loaded.is_empty() {
+            return;
+        }
+        let matchups = matchup_rows(&loaded, state.chart());
+        let [matchups_area, abilities_area, moves_area] = Layout::vertical([
+            Constraint::Length(matchups.len() as u16 + 3),
+            Constraint::Length(loaded.len() as u16 + 3),
+            Constraint::Fill(1),
+        ])
+        .areas(details_area);
+        render_matchups(&loaded, matchups, state.chart(), matchups_area, buf);
+
+        let names: Vec<String> = loaded.iter().map(|mon| mon.name.to_uppercase()).collect();
+        let abilities: Vec<Vec<String>> =
+            loaded.iter().map(|mon| mon.abilities.iter().flat_map(|a| &a.ability).map(|a| a.name.clone()).collect()).collect();
+        Paragraph::new(shared_lines(&names, &abilities))
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title("Abilities"))
+            .render(abilities_area, buf);
+        let moves: Vec<Vec<String>> =
+            loaded.iter().map(|mon| mon.moves.iter().map(|m| m.move_.name.clone()).collect()).collect();
+        Paragraph::new(shared_lines(&names, &moves))
+            .wrap(Wrap { trim: true })
+            .scroll((state.scroll(), 0))
+            .block(Block::bordered().title("Moves"))
+            .render(moves_area, buf);
+    }
+}
+
+fn render_column(state: &LoadingState, pinned: Option<[u32; 6]>, selected: bool, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered().border_style(if selected { Style::default().fg(Color::Blue) } else { Style::default() });
+    let LoadingState::Loaded(mon) = state else {
+        Paragraph::new(state.to_string()).wrap(Wrap { trim: true }).block(block).render(area, buf);
+        return;
+    };
+    let block = block.title(mon.name.to_uppercase());
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let rows = Layout::vertical([Constraint::Length(1); 9]).split(inner);
+    let types: Vec<Span> = mon
+        .types
+        .iter()
+        .filter_map(|t| MonType::from_str(&t.type_.name).ok())
+        .flat_map(|t| [type_span(t), Span::from(" ")])
+        .collect();
+    Line::from(types).render(rows[0], buf);
+
+    let stats = base_stats(&mon.stats);
+    for (i, stat) in MonStat::ALL.into_iter().enumerate() {
+        render_stat(stat, stats[i], pinned.map(|pinned| pinned[i]), rows[i + 2], buf);
+    }
+    let total: u32 = stats.iter().sum();
+    let mut line = vec![Span::from(format!("{:<6}{total:>3}", "TOTAL")).bold()];
+    line.extend(difference(total, pinned.map(|pinned| pinned.iter().sum())));
+    Line::from(line).render(rows[8], buf);
+}
+
+fn render_stat(stat: MonStat, value: u32, pinned: Option<u32>, area: Rect, buf: &mut Buffer) {
+    let [label_area, diff_area, gauge_area] =
+        Layout::horizontal([Constraint::Length(9), Constraint::Length(6), Constraint::Fill(1)]).areas(area);
+    Line::from(format!("{:<6}{value:>3}", stat.to_short_string())).render(label_area, buf);
+    Line::from(difference(value, pinned)).render(diff_area, buf);
+    Gauge::default()
+        .gauge_style(stat.bg())
+        .label("")
+        .ratio(value as f64 / 255f64)
+        .use_unicode(true)
+        .render(gauge_area, buf)
+}
+
+/// How far `value` is from the pinned Pokémon's, nothing for the pinned column itself
+fn difference<'a>(value: u32, pinned: Option<u32>) -> Vec<Span<'a>> {
+    let Some(pinned) = pinned else {
+        return Vec::new();
+    };
+    let difference = value as i64 - pinned as i64;
+    let span = Span::from(format!(" {difference:+}"));
+    vec![match difference {
+        0 => span.dark_gray(),
+        1.. => span.green(),
+        _ => span.red(),
+    }]
+}
+
+/// Multipliers of every attacking type that isn't neutral against at least one of them
+fn matchup_rows(loaded: &[&Pokemon], chart: TypeChart) -> Vec<(MonType, Vec<f32>)> {
+    let matchups: Vec<Matchups> = loaded
+        .iter()
+        .map(|mon| Matchups::new(&types_under(&mon.types, &mon.past_types, chart), chart, None))
+        .collect();
+    chart
+        .types()
+        .enumerate()
+        .map(|(i, attacking)| (attacking, matchups.iter().map(|matchups| matchups.multipliers[i].1).collect::<Vec<f32>>()))
+        .filter(|(_, multipliers)| multipliers.iter().any(|multiplier| *multiplier != 1.0))
+        .collect()
+}
+
+fn render_matchups(loaded: &[&Pokemon], rows: Vec<(MonType, Vec<f32>)>, chart: TypeChart, area: Rect, buf: &mut Buffer) {
+    let header = Row::new(std::iter::once(Cell::from("")).chain(loaded.iter().map(|mon| Cell::from(mon.name.to_uppercase()))))
+        .bold();
+    let rows = rows.into_iter().map(|(attacking, multipliers)| {
+        let cells = multipliers.into_iter().map(|multiplier| {
+            let text = Span::from(format!("×{}", format_multiplier(multiplier)));
+            Cell::from(match multiplier {
+                m if m > 1.0 => text.light_red(),
+                m if m < 1.0 => text.green(),
+                _ => text.dark_gray(),
+            })
+        });
+        Row::new(std::iter::once(Cell::from(type_span(attacking))).chain(cells))
+    });
+    let widths = std::iter::once(Constraint::Length(9)).chain(loaded.iter().map(|_| Constraint::Fill(1)));
+    Table::new(rows, widths)
+        .header(header)
+        .block(Block::bordered().title(format!("Matchups ({})", chart.name())))
+        .render(area, buf);
+}
+
+/// A line for what they all have, then one for what only each of them has
+fn shared_lines<'a>(names: &[String], lists: &[Vec<String>]) -> Vec<Line<'a>> {
+    let (shared, unique) = shared_and_unique(lists);
+    let list = |names: Vec<String>| match names.is_empty() {
+        true => Span::from("none").dark_gray(),
+        false => Span::from(names.join(", ").replace('-', " ")),
+    };
+    let mut lines = vec![Line::from(vec![Span::from(format!("Shared ({}) ", shared.len())).bold(), list(shared)])];
+    for (name, unique) in names.iter().zip(unique) {
+        lines.push(Line::from(vec![Span::from(format!("Only {name} ({}) ", unique.len())).bold(), list(unique)]));
+    }
+    lines
+}
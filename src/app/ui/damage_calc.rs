@@ -0,0 +1,139 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+
+use crate::{
+    app::screens::{
+        damage_calc::{DamageCalcScreen, DamageCalcState, DamageRow},
+        loading::LoadingState,
+    },
+    pokemon::{stats::StatSpread, MonStat},
+};
+
+impl Widget for &DamageCalcScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.read().unwrap();
+        let block = Block::bordered()
+            .title("Damage calculator")
+            .title_bottom("enter to type a name or toggle, +/- to change, backspace to go back")
+            .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() });
+        let [form, _, result] = Layout::vertical([
+            Constraint::Length(DamageRow::ALL.len() as u16),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(block.inner(area));
+
+        let (attack, defense) = state.category().stats();
+        let lines: Vec<Line> = DamageRow::ALL
+            .into_iter()
+            .map(|row| {
+                let (label, value) = row_text(&state, row, attack, defense);
+                let value = match state.editing() {
+                    Some(text) if row == state.selected() => Span::from(format!("{text}_")),
+                    _ => value,
+                };
+                let value = if row == state.selected() && state.focused() { value.black().on_blue() } else { value };
+                Line::from(vec![Span::from(format!("{label:<16}")).bold(), value])
+            })
+            .collect();
+        Paragraph::new(lines).render(form, buf);
+
+        let result_lines = match state.damage() {
+            Some(damage) => {
+                let (min, max) = damage.percent();
+                let rolls: Vec<String> = damage.rolls.iter().map(u32::to_string).collect();
+                vec![
+                    Line::from(vec![
+                        Span::from(format!("{}-{} damage", damage.min(), damage.max())).bold(),
+                        Span::from(format!(" of {} HP ({min:.1}% - {max:.1}%)", damage.hp)),
+                    ]),
+                    Line::from(damage.ko_chance().to_string()).bold(),
+                    Line::from(format!("Rolls: {}", rolls.join(", "))).dark_gray(),
+                ]
+            }
+            None if state.move_().loaded().is_some() && state.damage_move().is_none() => {
+                vec![Line::from("Status moves and moves without a fixed power deal no direct damage")]
+            }
+            None => vec![Line::from("Pick an attacker, a move and a defender").dark_gray()],
+        };
+        Paragraph::new(result_lines).wrap(Wrap { trim: true }).render(result, buf);
+        block.render(area, buf);
+    }
+}
+
+fn row_text(state: &DamageCalcState, row: DamageRow, attack: MonStat, defense: MonStat) -> (String, Span<'static>) {
+    let index = |stat: MonStat| MonStat::ALL.iter().position(|s| *s == stat).unwrap_or(0);
+    // Real stat value next to the EVs so the effect of the spread is visible
+    let stat = |slot: &LoadingState<_>, spread: &StatSpread, stat: MonStat| match slot.loaded() {
+        Some(mon) => format!(" ({})", DamageCalcState::combatant(mon, spread).stat(stat)),
+        None => String::new(),
+    };
+    let toggle = |on: bool| Span::from(if on { "yes" } else { "no" });
+    match row {
+        DamageRow::Attacker => ("Attacker".to_owned(), mon_span(state.attacker())),
+        DamageRow::AttackerLevel => ("  Level".to_owned(), Span::from(state.attacker_spread().level.to_string())),
+        DamageRow::AttackerNature => ("  Nature".to_owned(), Span::from(state.attacker_spread().nature.to_string())),
+        DamageRow::AttackerEvs => (
+            format!("  {} EVs", attack.to_short_string()),
+            Span::from(format!(
+                "{}{}",
+                state.attacker_spread().evs[index(attack)],
+                stat(state.attacker(), state.attacker_spread(), attack)
+            )),
+        ),
+        DamageRow::Move => (
+            "Move".to_owned(),
+            match state.move_() {
+                LoadingState::Loaded(move_) => match state.damage_move() {
+                    Some(damage_move) => Span::from(format!(
+                        "{} ({} {:?}, {} power)",
+                        move_.name.replace('-', " "),
+                        damage_move.type_,
+                        damage_move.category,
+                        damage_move.power
+                    )),
+                    None => Span::from(format!("{} (status)", move_.name.replace('-', " "))),
+                },
+                slot => Span::from(slot.to_string()),
+            },
+        ),
+        DamageRow::Defender => ("Defender".to_owned(), mon_span(state.defender())),
+        DamageRow::DefenderLevel => ("  Level".to_owned(), Span::from(state.defender_spread().level.to_string())),
+        DamageRow::DefenderNature => ("  Nature".to_owned(), Span::from(state.defender_spread().nature.to_string())),
+        DamageRow::DefenderHpEvs => (
+            "  HP EVs".to_owned(),
+            Span::from(format!(
+                "{}{}",
+                state.defender_spread().evs[index(MonStat::HP)],
+                stat(state.defender(), state.defender_spread(), MonStat::HP)
+            )),
+        ),
+        DamageRow::DefenderEvs => (
+            format!("  {} EVs", defense.to_short_string()),
+            Span::from(format!(
+                "{}{}",
+                state.defender_spread().evs[index(defense)],
+                stat(state.defender(), state.defender_spread(), defense)
+            )),
+        ),
+        DamageRow::Critical => ("Critical hit".to_owned(), toggle(state.conditions().critical)),
+        DamageRow::Weather => ("Weather".to_owned(), Span::from(state.conditions().weather.to_string())),
+        DamageRow::Burned => ("Attacker burned".to_owned(), toggle(state.conditions().burned)),
+        DamageRow::Generation => ("Generation".to_owned(), Span::from(state.conditions().chart.name())),
+    }
+}
+
+fn mon_span(slot: &LoadingState<rustemon::model::pokemon::Pokemon>) -> Span<'static> {
+    match slot {
+        LoadingState::Loaded(mon) => {
+            let types: Vec<String> = mon.types.iter().map(|t| t.type_.name.to_uppercase()).collect();
+            Span::from(format!("{} ({})", mon.name.to_uppercase(), types.join("/")))
+        }
+        slot => Span::from(slot.to_string()),
+    }
+}
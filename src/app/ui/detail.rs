@@ -0,0 +1,41 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+
+use crate::app::screens::{
+    detail::{Detail, DetailScreenState},
+    loading::LoadingState,
+};
+
+/// The screen border, with the loading state in the title and the keys at the bottom
+pub fn screen_block<T: Detail>(state: &DetailScreenState<T>, keys: &'static str) -> Block<'static> {
+    let loading_state = match state.loading_state() {
+        LoadingState::Loaded(resource) => format!("Loaded {0}", resource.name()),
+        loading_state => loading_state.to_string(),
+    };
+    let loading_state = Line::from(loading_state).alignment(Alignment::Right);
+    Block::bordered()
+        .title(loading_state)
+        .title_bottom(keys)
+        .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() })
+}
+
+/// The selected english flavor text, titled with the game it is from
+pub fn render_flavor_text<T: Detail>(resource: &T, selected: usize, area: Rect, buf: &mut Buffer) {
+    let flavor_texts = resource.flavor_texts();
+    let (title, text) = match flavor_texts.get(selected) {
+        Some((version_group, text)) => (
+            format!("Flavor text {0} ({1}/{2})", version_group, selected + 1, flavor_texts.len()),
+            text.replace(['\n', '\u{c}'], " "),
+        ),
+        None => ("Flavor text".to_owned(), String::new()),
+    };
+    Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .block(Block::bordered().title(title))
+        .render(area, buf);
+}
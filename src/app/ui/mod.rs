@@ -1,6 +1,11 @@
+pub mod ability_detail;
 pub mod app;
+pub mod compare;
+pub mod damage_calc;
+pub mod detail;
+pub mod move_detail;
+pub mod natures;
 pub mod pokedex;
-
-
-
-
+pub mod pokemon;
+pub mod team;
+pub mod type_chart;
@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, Paragraph, StatefulWidget, Widget, Wrap},
+};
+use rustemon::model::moves::{Move, MoveMetaData};
+
+use crate::{
+    app::{
+        screens::{loading::LoadingState, move_detail::MoveScreen},
+        ui::{
+            detail::{render_flavor_text, screen_block},
+            pokedex::detail::type_span,
+        },
+    },
+    pokemon::{MonStat, MonType},
+};
+
+impl Widget for &MoveScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = self.state.write().unwrap();
+        let block = screen_block(&state, "enter to open a Pokémon, h/l to change game, backspace to go back");
+        let LoadingState::Loaded(move_) = state.loading_state().clone() else {
+            block.render(area, buf);
+            return;
+        };
+        let [left, right] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(28)]).areas(block.inner(area));
+        let [header, _, stats, effect, meta, flavor_text] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Fill(1),
+        ])
+        .areas(left);
+
+        let mut title = vec![english_name(&move_).to_uppercase().bold(), Span::from("  ")];
+        if let Ok(type_) = MonType::from_str(&move_.type_.name) {
+            title.push(type_span(type_));
+            title.push(Span::from(" "));
+        }
+        title.push(Span::from(move_.damage_class.name.to_uppercase()));
+        Line::from(title).render(header, buf);
+
+        Paragraph::new(vec![
+            field("Power", optional(move_.power)),
+            field("Accuracy", move_.accuracy.map_or("-".to_owned(), |accuracy| format!("{accuracy}%"))),
+            field("PP", optional(move_.pp)),
+            field("Priority", format!("{:+}", move_.priority)),
+            field("Target", move_.target.name.replace('-', " ")),
+            field("Generation", move_.generation.name.trim_start_matches("generation-").to_uppercase()),
+            field("Stats", stat_changes(&move_)),
+        ])
+        .render(stats, buf);
+
+        let effect_text = move_
+            .effect_entries
+            .iter()
+            .find(|entry| entry.language.name == "en")
+            .map(|entry| {
+                let chance = move_.effect_chance.map(|chance| chance.to_string()).unwrap_or_default();
+                entry.effect.replace("$effect_chance", &chance)
+            })
+            .unwrap_or_default();
+        Paragraph::new(effect_text)
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title("Effect"))
+            .render(effect, buf);
+
+        Paragraph::new(move_.meta.as_ref().map(meta_lines).unwrap_or_default())
+            .block(Block::bordered().title("Battle effects"))
+            .render(meta, buf);
+
+        render_flavor_text(&*move_, state.flavor_text(), flavor_text, buf);
+
+        let learners = List::new(
+            move_
+                .learned_by_pokemon
+                .iter()
+                .map(|pokemon| pokemon.name.to_uppercase()),
+        )
+        .block(Block::bordered().title(format!("Learned by ({0})", move_.learned_by_pokemon.len())))
+        .highlight_style(Style::new().black().on_blue());
+        StatefulWidget::render(learners, right, buf, &mut state.pokemon);
+        block.render(area, buf);
+    }
+}
+
+fn english_name(move_: &Move) -> String {
+    move_
+        .names
+        .iter()
+        .find(|name| name.language.name == "en")
+        .map(|name| name.name.clone())
+        .unwrap_or_else(|| move_.name.clone())
+}
+
+fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+    Line::from(vec![Span::from(format!("{label:<12}")).bold(), Span::from(value)])
+}
+
+fn optional(value: Option<i64>) -> String {
+    value.map_or("-".to_owned(), |value| value.to_string())
+}
+
+fn stat_changes(move_: &Move) -> String {
+    if move_.stat_changes.is_empty() {
+        return "-".to_owned();
+    }
+    let changes = move_
+        .stat_changes
+        .iter()
+        .map(|change| {
+            let stat = MonStat::from_str(&change.stat.name)
+                .map(|stat| stat.to_short_string())
+                .unwrap_or_else(|_| change.stat.name.to_uppercase());
+            format!("{stat} {:+}", change.change)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    match move_.meta.as_ref().map(|meta| meta.stat_chance) {
+        Some(chance) if chance > 0 => format!("{changes} ({chance}%)"),
+        _ => changes,
+    }
+}
+
+fn meta_lines(meta: &MoveMetaData) -> Vec<Line<'_>> {
+    let range = |min: Option<i64>, max: Option<i64>| match (min, max) {
+        (Some(min), Some(max)) if min != max => format!("{min}-{max}"),
+        (Some(count), _) | (_, Some(count)) => count.to_string(),
+        (None, None) => "-".to_owned(),
+    };
+    let chance = |chance: i64| if chance == 0 { String::new() } else { format!(" ({chance}%)") };
+    vec![
+        field("Category", meta.category.name.replace('+', " + ").replace('-', " ")),
+        field("Ailment", format!("{}{}", meta.ailment.name.replace('-', " "), chance(meta.ailment_chance))),
+        field("Hits", range(meta.min_hits, meta.max_hits)),
+        field("Turns", range(meta.min_turns, meta.max_turns)),
+        field("Drain/Heal", format!("{:+}% / {:+}%", meta.drain, meta.healing)),
+        field("Crit/Flinch", format!("{:+} stage / {}%", meta.crit_rate, meta.flinch_chance)),
+    ]
+}
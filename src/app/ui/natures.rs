@@ -0,0 +1,68 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{
+    app::screens::natures::NaturesScreen,
+    pokemon::{nature::{Nature, AFFECTED}, MonStat},
+};
+
+impl Widget for &NaturesScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.read().unwrap();
+        let selected = state.selected();
+        let block = Block::bordered()
+            .title("Natures")
+            .title_bottom("rows raise a stat by 10%, columns lower one, backspace to go back")
+            .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() });
+        let [table_area, _, summary_area] = Layout::vertical([
+            Constraint::Length(AFFECTED.len() as u16 + 1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(block.inner(area));
+
+        let header = Row::new(
+            std::iter::once(Cell::from("+ \\ -"))
+                .chain(AFFECTED.iter().map(|stat| Cell::from(stat_span(*stat, "-")))),
+        );
+        let rows = AFFECTED.iter().enumerate().map(|(row, raised)| {
+            Row::new(std::iter::once(Cell::from(stat_span(*raised, "+"))).chain(
+                Nature::ALL[row * AFFECTED.len()..(row + 1) * AFFECTED.len()].iter().map(|nature| {
+                    let cell = Cell::from(nature.to_string());
+                    if nature.raised().is_none() { cell.dark_gray() } else { cell }
+                }),
+            ))
+        });
+        let widths = std::iter::once(Constraint::Length(8)).chain(AFFECTED.iter().map(|_| Constraint::Length(9)));
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(Style::default().bg(Color::DarkGray))
+            .column_highlight_style(Style::default().bg(Color::DarkGray))
+            .cell_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut table_state = TableState::default()
+            .with_selected(Some(selected as usize / AFFECTED.len()))
+            .with_selected_column(Some(selected as usize % AFFECTED.len() + 1));
+        StatefulWidget::render(table, table_area, buf, &mut table_state);
+
+        let mut summary = vec![Span::from(selected.to_string()).bold(), Span::from(": ")];
+        match (selected.raised(), selected.lowered()) {
+            (Some(raised), Some(lowered)) => {
+                summary.push(stat_span(raised, "+"));
+                summary.push(Span::from(" "));
+                summary.push(stat_span(lowered, "-"));
+            }
+            _ => summary.push(Span::from("no effect")),
+        }
+        Line::from(summary).render(summary_area, buf);
+        block.render(area, buf);
+    }
+}
+
+fn stat_span(stat: MonStat, sign: &str) -> Span<'static> {
+    Span::from(format!("{sign}{}", stat.to_short_string())).bg(stat.bg()).fg(Color::Black)
+}
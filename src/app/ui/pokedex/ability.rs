@@ -1,4 +1,4 @@
-use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, text::{Line, Text}, widgets::{Block, Paragraph, Widget, Wrap}};
+use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, text::Line, widgets::{Block, Paragraph, Widget, Wrap}};
 use crate::app::widgets::pokedex::ability::AbilityWidget;
 
 impl Widget for AbilityWidget {
@@ -14,7 +14,7 @@ impl Widget for AbilityWidget {
             {
                 let block = Block::default();
                 let [name_area, hidden_area] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(6)]).areas(block.inner(header));
-                Line::from(ability.ability.name.to_string()).render(name_area, buf);
+                Line::from(ability.name.to_string()).render(name_area, buf);
                 if state.hidden()
                 {
 
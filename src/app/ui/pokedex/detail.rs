@@ -5,12 +5,13 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Block, Gauge, Widget},
+    widgets::{Block, Paragraph, Widget, Wrap},
 };
-use rustemon::model::pokemon::{PokemonMove, PokemonStat, PokemonType};
+use rustemon::model::pokemon::{PokemonSpecies, PokemonType};
 
 use crate::{
-    app::widgets::pokedex::detail::{LoadingState, DetailsWidget}, pokemon::{MonStat, MonType}
+    app::widgets::pokedex::detail::{LoadingState, DetailsWidget, SpeciesLoadingState},
+    pokemon::{effectiveness::{format_multiplier, types_under, Matchups, TypeChart}, learnset::versions, MonType},
 };
 
 impl Widget for &DetailsWidget {
@@ -30,17 +31,34 @@ impl Widget for &DetailsWidget {
                     let [left, right] =
                         Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
                             .areas(block.inner(area));
-                    let [name, types, _, stats, abilities] = Layout::vertical([
+                    let [name, types, _, stats, abilities, matchups, evolution] = Layout::vertical([
                         Constraint::Length(1),
                         Constraint::Length(1),
                         Constraint::Length(1),
+                        Constraint::Length(if self.stats.state.read().unwrap().calculator() { 11 } else { 8 }),
                         Constraint::Length(8),
-                        Constraint::Length(8),
+                        Constraint::Length(5),
+                        Constraint::Fill(1),
                     ])
                     .areas(left);
-                    Span::from(pokemon.name.to_uppercase()).bold().render(name, buf);
+                    let mut name_line = Line::from(Span::from(pokemon.name.to_uppercase()).bold());
+                    if let SpeciesLoadingState::Loaded(species) = state.species() {
+                        if let Some(genus) = species.genera.iter().find(|genus| genus.language.name == "en") {
+                            name_line.push_span(format!("  {}", genus.genus));
+                        }
+                        let forms = species.varieties.len();
+                        if forms > 1 {
+                            let form = species
+                                .varieties
+                                .iter()
+                                .position(|variety| variety.pokemon.name == pokemon.name)
+                                .map_or(0, |i| i + 1);
+                            name_line.push_span(Span::from(format!("  form {form}/{forms}, f to change")).dark_gray());
+                        }
+                    }
+                    name_line.render(name, buf);
                     render_types(&pokemon.types, types, buf);
-                    render_stats(&pokemon.stats, stats, buf);
+                    self.stats.render(stats, buf);
                     // render_abilities(
                     //     &pokemon.abilities,
                     //     &mut state.ability_table_state,
@@ -48,7 +66,14 @@ impl Widget for &DetailsWidget {
                     //     buf,
                     // );
                     self.abilities.clone().render(abilities, buf);
-                    self.moves.clone().render(right, buf);
+                    let chart = state.type_chart();
+                    render_matchups(&types_under(&pokemon.types, &pokemon.past_types, chart), chart, matchups, buf);
+                    self.evolution.render(evolution, buf);
+                    let [species, moves] =
+                        Layout::vertical([Constraint::Length(9), Constraint::Fill(1)]).areas(right);
+                    let version_group = self.moves.state.read().unwrap().version_group().map(str::to_owned);
+                    render_species(state.species(), version_group.as_deref(), species, buf);
+                    self.moves.clone().render(moves, buf);
                     // render_moves(&pokemon.moves, right, buf);
                     block.render(area, buf);
                 
@@ -58,31 +83,6 @@ impl Widget for &DetailsWidget {
     }
 }
 
-fn render_stats(stats: &Vec<PokemonStat>, area: Rect, buf: &mut Buffer) {
-    fn render_stat(stat: MonStat, value: i64, area: Rect, buf: &mut Buffer) {
-        let [label_area, value_area, gauge_area] = Layout::horizontal([
-            Constraint::Length(6),
-            Constraint::Length(3),
-            Constraint::Fill(1),
-        ])
-        .areas(area);
-        Text::from(stat.to_short_string()).render(label_area, buf);
-        Text::from(value.to_string()).render(value_area, buf);
-        Gauge::default()
-            .gauge_style(stat.bg())
-            .label("")
-            .ratio((value) as f64 / 255f64)
-            .use_unicode(true)
-            .render(gauge_area, buf)
-    }
-    let [hp, atk, def, satk, sdef, spd] = Layout::vertical([Constraint::Length(1); 6]).areas(area);
-    render_stat(MonStat::HP, stats[0].base_stat, hp, buf);
-    render_stat(MonStat::Attack, stats[1].base_stat, atk, buf);
-    render_stat(MonStat::Defense, stats[2].base_stat, def, buf);
-    render_stat(MonStat::SpecialAttack, stats[3].base_stat, satk, buf);
-    render_stat(MonStat::SpecialDefense, stats[4].base_stat, sdef, buf);
-    render_stat(MonStat::Speed, stats[5].base_stat, spd, buf);
-}
 // fn render_abilities(
 //     abilities: &Vec<PokemonAbility>,
 //     state: &mut TableState,
@@ -107,11 +107,7 @@ fn render_stats(stats: &Vec<PokemonStat>, area: Rect, buf: &mut Buffer) {
 //     let list = Table::new(rows, widths).block(block);
 //     StatefulWidget::render(list, area, buf, state);
 // }
-fn render_moves(moves: &Vec<PokemonMove>, area: Rect, buf: &mut Buffer) {
-    let block = Block::bordered().title("Moves");
-    block.render(area, buf)
-}
-fn render_types(types: &Vec<PokemonType>, area: Rect, buf: &mut Buffer) {
+fn render_types(types: &[PokemonType], area: Rect, buf: &mut Buffer) {
     let chunks = Layout::horizontal([ratatui::layout::Constraint::Length(8); 2]).split(area);
     for t in types {
         type_span(MonType::from_str(&t.type_.name).unwrap())
@@ -119,7 +115,92 @@ fn render_types(types: &Vec<PokemonType>, area: Rect, buf: &mut Buffer) {
     }
 }
 
-fn type_span<'a>(type_: MonType) -> Span<'a> {
+fn render_species(species: &SpeciesLoadingState, version_group: Option<&str>, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered().title("Species");
+    let SpeciesLoadingState::Loaded(species) = species else {
+        Paragraph::new(species.to_string()).block(block).render(area, buf);
+        return;
+    };
+    let [info, flavor_text] =
+        Layout::vertical([Constraint::Length(4), Constraint::Fill(1)]).areas(block.inner(area));
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![Span::from(format!("{label:<12}")).bold(), Span::from(value)])
+    };
+    let egg_groups: Vec<String> = species.egg_groups.iter().map(|group| group.name.replace('-', " ")).collect();
+    // Females per eight, -1 for genderless species
+    let gender = match species.gender_rate {
+        -1 => "genderless".to_owned(),
+        rate => format!("{}% ♂  {}% ♀", 100.0 - rate as f64 * 12.5, rate as f64 * 12.5),
+    };
+    let happiness = species.base_happiness.map_or("-".to_owned(), |happiness| happiness.to_string());
+    let hatch = species.hatch_counter.map_or("-".to_owned(), |cycles| format!("{cycles} cycles"));
+    Paragraph::new(vec![
+        field("Egg groups", egg_groups.join(", ")),
+        field("Gender", gender),
+        Line::from(vec![
+            Span::from(format!("{:<12}", "Catch rate")).bold(),
+            Span::from(format!("{:<8}", species.capture_rate)),
+            Span::from("Happiness ").bold(),
+            Span::from(happiness),
+        ]),
+        Line::from(vec![
+            Span::from(format!("{:<12}", "Growth")).bold(),
+            Span::from(format!("{:<16}", species.growth_rate.name.replace('-', " "))),
+            Span::from("Hatch ").bold(),
+            Span::from(hatch),
+        ]),
+    ])
+    .render(info, buf);
+
+    let (version, text) = flavor_text_for(species, version_group);
+    Paragraph::new(text.replace(['\n', '\u{c}'], " "))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().title(format!("Pokédex entry {version}")).title_style(Style::default().bold()))
+        .render(flavor_text, buf);
+    block.render(area, buf);
+}
+
+/// English entry from a version of `version_group`, or the newest one when that game has none
+fn flavor_text_for(species: &PokemonSpecies, version_group: Option<&str>) -> (String, String) {
+    let versions = version_group.map(versions).unwrap_or_default();
+    let english = || species.flavor_text_entries.iter().filter(|entry| entry.language.name == "en");
+    english()
+        .find(|entry| entry.version.as_ref().is_some_and(|version| versions.contains(&version.name.as_str())))
+        .or_else(|| english().next_back())
+        .map(|entry| {
+            let version = entry.version.as_ref().map(|version| version.name.clone()).unwrap_or_default();
+            (version, entry.flavor_text.clone())
+        })
+        .unwrap_or_default()
+}
+
+fn render_matchups(types: &[MonType], chart: TypeChart, area: Rect, buf: &mut Buffer) {
+    fn matchup_line<'a>(label: &'a str, matchups: &[(MonType, f32)]) -> Line<'a> {
+        let mut spans = vec![Span::from(format!("{label:<7}"))];
+        for (type_, multiplier) in matchups {
+            spans.push(type_span(*type_));
+            if *multiplier != 0.0 {
+                spans.push(Span::from(format!("×{}", format_multiplier(*multiplier))));
+            }
+            spans.push(Span::from(" "));
+        }
+        Line::from(spans)
+    }
+    let matchups = Matchups::new(types, chart, None);
+    let immunities: Vec<(MonType, f32)> = matchups.immunities().into_iter().map(|t| (t, 0.0)).collect();
+    let block = Block::bordered()
+        .title(format!("Matchups ({})", chart.name()))
+        .title_bottom("g to change generation");
+    Text::from(vec![
+        matchup_line("Weak", &matchups.weaknesses()),
+        matchup_line("Resist", &matchups.resistances()),
+        matchup_line("Immune", &immunities),
+    ])
+    .render(block.inner(area), buf);
+    block.render(area, buf);
+}
+
+pub fn type_span<'a>(type_: MonType) -> Span<'a> {
     Span::styled(
         type_.to_string().to_uppercase(),
         Style::default().bg(type_.bg()).fg(type_.fg()),
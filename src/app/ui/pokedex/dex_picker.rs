@@ -0,0 +1,40 @@
+use ratatui::{buffer::Buffer, layout::{Constraint, Flex, Layout, Rect}, style::{Color, Style, Stylize}, text::Line, widgets::{Block, Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Widget}};
+
+use crate::app::widgets::pokedex::dex_picker::{DexPickerWidget, LoadingState};
+
+impl Widget for &DexPickerWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.read().unwrap();
+        let [area] = Layout::horizontal([Constraint::Length(40)]).flex(Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Percentage(80)]).flex(Flex::Center).areas(area);
+        let block = Block::bordered()
+            .title("Pokédexes")
+            .title_bottom("enter to pick, esc to close")
+            .border_style(Style::default().fg(Color::Blue));
+        Clear.render(area, buf);
+        match state.loading_state() {
+            LoadingState::Loaded(groups) => {
+                let mut items = Vec::new();
+                let mut selected_row = None;
+                let mut dex_index = 0;
+                for group in groups {
+                    items.push(ListItem::new(Line::from(group.region.to_uppercase()).bold()));
+                    for dex in &group.dexes {
+                        if dex_index == state.selected() {
+                            selected_row = Some(items.len());
+                        }
+                        let marker = if dex == state.current() { "* " } else { "  " };
+                        items.push(ListItem::new(format!("{marker}{}", dex.replace('-', " "))));
+                        dex_index += 1;
+                    }
+                }
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(">>")
+                    .highlight_style(Style::new().black().on_blue());
+                StatefulWidget::render(list, area, buf, &mut ListState::default().with_selected(selected_row));
+            }
+            loading_state => Paragraph::new(loading_state.to_string()).block(block).render(area, buf),
+        }
+    }
+}
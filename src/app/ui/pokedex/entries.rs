@@ -1,6 +1,15 @@
-use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, style::{Color, Style, Stylize}, widgets::{Block, Row, StatefulWidget, Table, Widget}};
+use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, style::{Color, Style, Stylize}, text::{Line, Span}, widgets::{Block, Paragraph, Row, StatefulWidget, Table, Widget, Wrap}};
 
-use crate::app::widgets::pokedex::entries::EntriesWidget;
+use rustemon::model::games::PokemonEntry;
+
+use crate::{
+    app::widgets::pokedex::entries::{EntriesState, EntriesWidget, SortColumn},
+    collection::Marks,
+    pokemon::{filter::StatKey, MonType},
+};
+
+/// Width the entries need with the types and stats shown
+pub const WIDE_WIDTH: u16 = 76;
 
 impl Widget for &mut EntriesWidget {
     fn render(self, area: Rect, buf: &mut Buffer)
@@ -8,27 +17,143 @@ impl Widget for &mut EntriesWidget {
         Self: Sized,
     {
         let mut state = self.state.write().unwrap();
+        let status = query_status(&state);
+        // Errors are long and need the extra lines to wrap into, with one spare for word breaks
+        let width = area.width.saturating_sub(2).max(1) as usize;
+        let status_height = match &status {
+            Some(line) if state.query_error().is_some() => line.width().div_ceil(width) as u16 + 1,
+            Some(_) => 1,
+            None => 0,
+        };
         let [list_area, query] =
-            Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(area);
-        let block = Block::bordered()
-            .title(format!("Entries"))
-            .title_bottom("j/k to scroll").border_style(if state.focused(){Style::default().fg(Color::Blue)} else {Style::default()});
-        let rows: Vec<Row> = state
-            .entries()
-            .iter()
-            .map(|entry| {
-                Row::new(vec![
-                    format!("#{:0>4}", entry.entry_number),
-                    entry.pokemon_species.name.to_uppercase(),
-                ])
-            })
-            .collect();
-        let widths = [Constraint::Length(5), Constraint::Fill(1)];
-        let table = Table::new(rows, widths)
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(3 + status_height)]).areas(area);
+        let caught = state.completion();
+        let total = state.entries().len();
+        let title = match state.sort() {
+            (SortColumn::Number, false) => "Entries".to_owned(),
+            (column, descending) => format!("Entries {}{}", if descending { "↓" } else { "↑" }, column.to_short_string()),
+        };
+        let mut block = Block::bordered()
+            .title(title)
+            .title(Line::from(format!("{caught}/{total}")).right_aligned().dark_gray())
+            .title_bottom(if state.missing_only() { "missing, m for all" } else { "F/S/C mark, m missing" })
+            .border_style(if state.focused(){Style::default().fg(Color::Blue)} else {Style::default()});
+        if state.wide() {
+            block = block.title_bottom(Line::from("o/O to sort, w to hide stats").right_aligned());
+        }
+        if let Some(error) = state.save_error() {
+            block = block.title_bottom(Line::from(format!("Couldn't save: {error}")).red());
+        }
+        let rows: Vec<Row> = state.visible_entries().map(|entry| entry_row(&state, entry)).collect();
+        let mut widths = vec![Constraint::Length(5), Constraint::Length(2), Constraint::Fill(1)];
+        let mut table = Table::default();
+        if state.wide() {
+            widths.push(Constraint::Length(7));
+            widths.extend(StatKey::ALL.map(|_| Constraint::Length(5)));
+            table = table.header(header(state.sort().0));
+        }
+        let table = table
+            .rows(rows)
+            .widths(widths)
             .block(block)
             .highlight_spacing(ratatui::widgets::HighlightSpacing::Always)
             .highlight_symbol(">>")
             .row_highlight_style(Style::new().black().on_blue());
-        StatefulWidget::render(table, list_area, buf, &mut state.table_state());
+        StatefulWidget::render(table, list_area, buf, state.table_state());
+        render_query(state.query(), state.editing(), status, query, buf);
+    }
+}
+
+fn entry_row<'a>(state: &EntriesState, entry: &PokemonEntry) -> Row<'a> {
+    let name = &entry.pokemon_species.name;
+    let mut cells = vec![
+        Line::from(format!("#{:0>4}", entry.entry_number)),
+        marker(state.marks(name)),
+        Line::from(name.to_uppercase()),
+    ];
+    if !state.wide() {
+        return Row::new(cells);
+    }
+    // The background loader fills these in as it goes
+    let Some(index_entry) = state.index_entry(name) else {
+        cells.push(Line::from("…").dark_gray());
+        return Row::new(cells);
+    };
+    cells.push(Line::from(index_entry.types.iter().flat_map(|t| [short_type_span(*t), Span::from(" ")]).collect::<Vec<_>>()));
+    cells.extend(StatKey::ALL.map(|key| Line::from(index_entry.stat(key).to_string()).right_aligned()));
+    Row::new(cells)
+}
+
+/// Column names, with the one the entries are sorted by picked out
+fn header<'a>(sort: SortColumn) -> Row<'a> {
+    let label = |column: SortColumn, text: String| {
+        let span = Span::from(text);
+        if column == sort { span.black().on_yellow() } else { span }
+    };
+    let mut cells = vec![
+        Line::from(label(SortColumn::Number, "#".to_owned())),
+        Line::from(""),
+        Line::from(label(SortColumn::Name, "NAME".to_owned())),
+        Line::from("TYPES"),
+    ];
+    cells.extend(StatKey::ALL.map(|key| Line::from(label(SortColumn::Stat(key), key.to_short_string())).right_aligned()));
+    Row::new(cells).bold()
+}
+
+/// Three letters is enough to tell the types apart and keeps two of them in a narrow column
+fn short_type_span<'a>(type_: MonType) -> Span<'a> {
+    let name: String = type_.to_string().to_uppercase().chars().take(3).collect();
+    Span::styled(name, Style::default().bg(type_.bg()).fg(type_.fg()))
+}
+
+/// Favourite star then a dot that fills in once the species is caught
+fn marker(marks: Marks) -> Line<'static> {
+    let favorite = if marks.favorite { Span::from("★").yellow() } else { Span::from(" ") };
+    let progress = match (marks.caught, marks.seen) {
+        (true, _) => Span::from("●").green(),
+        (false, true) => Span::from("○"),
+        (false, false) => Span::from(" "),
+    };
+    Line::from(vec![favorite, progress])
+}
+
+/// Why the query can't be used, or how far the index the filters need has got
+fn query_status(state: &EntriesState) -> Option<Line<'static>> {
+    if let Some(error) = state.query_error() {
+        return Some(Line::from(error.to_owned()).red());
+    }
+    let (indexed, failed) = state.index_progress()?;
+    let total = state.entries().len();
+    let mut line = match state.indexing() {
+        true => Line::from(format!("indexing {indexed}/{total}")).yellow(),
+        false => Line::from(format!("indexed {indexed}/{total}")).dark_gray(),
+    };
+    if failed > 0 {
+        line.push_span(Span::from(format!(", {failed} failed")).red());
+    }
+    Some(line)
+}
+
+fn render_query(query: &str, editing: bool, status: Option<Line<'static>>, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered()
+        .title("Search")
+        .title_bottom(if editing { "enter/esc" } else { "/ search, w stats" })
+        .border_style(if editing { Style::default().fg(Color::Blue) } else { Style::default() });
+    // Only the end of a long query is shown, that's where the typing happens
+    let width = area.width.saturating_sub(3) as usize;
+    let skip = query.chars().count().saturating_sub(width);
+    let mut line = Line::from(Span::from(query.chars().skip(skip).collect::<String>()));
+    if editing {
+        line.push_span(Span::from(" ").reversed());
+        if query.is_empty() {
+            line.push_span(Span::from("name type:fire spe>=100 ability:x move:x gen:4").dark_gray());
+        }
+    }
+    let inner = block.inner(area);
+    block.render(area, buf);
+    let [query_area, status_area] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+    line.render(query_area, buf);
+    if let Some(status) = status {
+        Paragraph::new(status).wrap(Wrap { trim: true }).render(status_area, buf);
     }
 }
@@ -0,0 +1,35 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::{Color, Style, Stylize}, text::{Line, Span}, widgets::{Block, List, ListItem, Paragraph, StatefulWidget, Widget}};
+
+use crate::app::widgets::pokedex::evolution::{EvolutionWidget, LoadingState};
+
+impl Widget for &EvolutionWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = self.state.write().unwrap();
+        let block = Block::bordered()
+            .title("Evolution")
+            .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() });
+        let LoadingState::Loaded(steps) = state.loading_state().clone() else {
+            Paragraph::new(state.loading_state().to_string()).block(block).render(area, buf);
+            return;
+        };
+        let items = steps.iter().map(|step| {
+            let indent = match step.depth {
+                0 => String::new(),
+                depth => format!("{}└ ", "  ".repeat(depth - 1)),
+            };
+            let name = Span::from(step.species.to_uppercase());
+            let name = if step.species == state.species() { name.bold() } else { name };
+            ListItem::new(Line::from(vec![
+                Span::from(indent),
+                name,
+                Span::from(format!("  {}", step.conditions)).dark_gray(),
+            ]))
+        });
+        let list = List::new(items).block(block).highlight_style(if state.focused() {
+            Style::new().black().on_blue()
+        } else {
+            Style::new()
+        });
+        StatefulWidget::render(list, area, buf, &mut state.list_state);
+    }
+}
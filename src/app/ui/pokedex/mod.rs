@@ -1,7 +1,10 @@
 pub mod screen;
 pub mod entries;
+pub mod dex_picker;
 pub mod detail;
+pub mod evolution;
 pub mod abilities;
 pub mod ability;
 pub mod moves;
-pub mod monmove;
\ No newline at end of file
+pub mod monmove;
+pub mod stats;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, text::{Line, Text}, widgets::{Block, Paragraph, Widget, Wrap}};
+use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, text::Line, widgets::{Block, Paragraph, Widget, Wrap}};
 
 use crate::app::widgets::pokedex::monmove::{self, LoadingState, MoveWidget};
 
@@ -11,15 +11,15 @@ impl Widget for MoveWidget {
         let [header, body] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
         match state.loading_state() {
             LoadingState::Idle => Block::default().render(area, buf),
-            LoadingState::Loading(pokemon_move)  | LoadingState::Lazy(pokemon_move)=> 
+            LoadingState::Loading(_)  | LoadingState::Lazy(_)=> 
             {
                 let block = Block::default();
-                let [name_area, hidden_area] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(6)]).areas(block.inner(header));
+                let [name_area, _] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(6)]).areas(block.inner(header));
                 Line::from(state.loading_state().to_string()).render(name_area, buf);
             },
             monmove::LoadingState::Loaded(move_) => {
                 let block = Block::default().style(self.style);
-                let [name_area, hidden_area] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(6)]).areas(block.inner(header));
+                let [name_area, _] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(6)]).areas(block.inner(header));
                 
                 for name in &move_.names
                 {
@@ -1,10 +1,10 @@
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Block;
 use ratatui::{buffer::Buffer, layout::Rect};
 use ratatui::prelude::*;
 use tui_widget_list::{ListBuilder,ListView};
 
-use crate::app::widgets;
-use crate::app::widgets::pokedex::moves::MovesWidget;
+use crate::app::widgets::pokedex::moves::{MoveEntry, MovesWidget};
+use crate::pokemon::learnset::LearnMethod;
 
 
 
@@ -14,17 +14,49 @@ impl Widget for &MovesWidget {
         let widgets = state.widgets.clone();
         let focused = state.focused();
         let builder = ListBuilder::new(move |context| {
-            let mut widget = widgets[context.index].clone();
+            let mut entry = widgets[context.index].clone();
             if  focused && context.is_selected
             {
-                widget.style = Style::default().bg(Color::Blue).fg(Color::Black);
+                entry.widget.style = Style::default().bg(Color::Blue).fg(Color::Black);
             }
-            (widget, 3)
+            let height = if entry.header.is_some() { 4 } else { 3 };
+            (entry, height)
         });
         let item_count = state.widgets.len();
-        let block = Block::bordered().border_style(if state.focused(){Style::default().fg(Color::Blue)} else {Style::default()});
+        let title = match state.version_group() {
+            Some(group) => format!("Moves in {group}"),
+            None => "Moves".to_owned(),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .title_bottom("v to change game")
+            .border_style(if state.focused(){Style::default().fg(Color::Blue)} else {Style::default()});
         let list = ListView::new(builder, item_count).infinite_scrolling(false).block(block);
         
         list.render(area, buf, &mut state.list_state);
     }
-}
\ No newline at end of file
+}
+
+impl Widget for MoveEntry {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = match &self.header {
+            Some(method) => {
+                let [header, rest] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+                Line::from(method.to_string().to_uppercase()).bold().underlined().render(header, buf);
+                rest
+            }
+            None => area,
+        };
+        let [_, label_area] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(6)])
+            .areas(Rect { height: 1, ..area });
+        let style = self.widget.style;
+        self.widget.render(area, buf);
+        // Level 0 level-up moves are the ones learned on evolution
+        let label = match (&self.method, self.level) {
+            (LearnMethod::LevelUp, 0) => "Evo".to_owned(),
+            (LearnMethod::LevelUp, level) => format!("Lv {level}"),
+            _ => String::new(),
+        };
+        Line::from(label).style(style).right_aligned().render(label_area, buf);
+    }
+}
@@ -1,22 +1,49 @@
-use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, text::Line, widgets::{Block, Widget}};
+use ratatui::{buffer::Buffer, layout::{Constraint, Layout, Rect}, style::Stylize, text::{Line, Span}, widgets::{Block, Widget}};
 
-use crate::app::screens::pokedex::{LoadingState, PokedexScreen};
+use crate::app::{screens::pokedex::{LoadingState, PokedexScreen, PokedexState}, ui::pokedex::entries::WIDE_WIDTH};
 
 impl Widget for &mut PokedexScreen {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let state = self.state.read().unwrap();
         let loading_state = Line::from(format!("{0}", state.loading_state()));
 
+        // Switching dex keeps the old entries up until the new ones arrive
+        let (has_entries, wide) = {
+            let entries = self.entries.state.read().unwrap();
+            (!entries.entries().is_empty(), entries.wide())
+        };
         match state.loading_state() {
-            LoadingState::Loaded(_) => {
-                let block = Block::default().title(loading_state);
-                let chunks = Layout::horizontal([Constraint::Length(24), Constraint::Min(0)])
+            LoadingState::Loading(_) | LoadingState::Error(_) if !has_entries => {
+                Block::default().title(loading_state).title(warm_status(&state)).render(area, buf)
+            }
+            _ => {
+                let block = Block::default().title(loading_state).title(warm_status(&state));
+                let chunks = Layout::horizontal([Constraint::Length(if wide { WIDE_WIDTH } else { 24 }), Constraint::Min(0)])
                     .split(block.inner(area));
                 self.entries.render(chunks[0], buf);
                 self.detail_view.render(chunks[1], buf);
                 block.render(area, buf);
             }
-            _ => Block::default().title(loading_state).render(area, buf),
         }
+        if self.dex_picker.is_open() {
+            self.dex_picker.render(area, buf);
+        }
+    }
+}
+
+/// How far filling the cache got, or the key that starts it
+fn warm_status(state: &PokedexState) -> Line<'static> {
+    let Some(progress) = state.warm_progress() else {
+        return Line::from("W to warm the cache").dark_gray().right_aligned();
+    };
+    let counts = format!("{}/{}", progress.done, progress.total);
+    let mut line = match (state.warming(), progress.done == progress.total) {
+        (true, _) => Line::from(format!("warming {counts}")).yellow(),
+        (false, true) => Line::from(format!("warmed {counts}")).dark_gray(),
+        (false, false) => Line::from(format!("stopped warming at {counts}")).dark_gray(),
+    };
+    if progress.failed > 0 {
+        line.push_span(Span::from(format!(", {} failed", progress.failed)).red());
     }
+    line.right_aligned()
 }
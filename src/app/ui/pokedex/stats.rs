@@ -0,0 +1,101 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Gauge, Widget},
+};
+
+use crate::{
+    app::widgets::pokedex::stats::{StatsColumn, StatsWidget, SPREAD_ROWS},
+    pokemon::{stats::{stat_range, MAX_TOTAL_EVS}, MonStat},
+};
+
+impl Widget for &StatsWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.read().unwrap();
+        let base = state.base();
+        if !state.calculator() {
+            let rows = Layout::vertical([Constraint::Length(1); 6]).split(area);
+            for (i, stat) in MonStat::ALL.into_iter().enumerate() {
+                render_base_stat(stat, base[i], rows[i], buf);
+            }
+            return;
+        }
+        let spread = state.spread();
+        let block = Block::bordered()
+            .title("Stat calculator")
+            .title_bottom("+/- to change, s to close")
+            .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() });
+        let (row, column) = state.selected();
+        let highlight = |span: Span<'static>, selected: bool| {
+            if selected && state.focused() { span.black().on_blue() } else { span }
+        };
+        let nature = match (spread.nature.raised(), spread.nature.lowered()) {
+            (Some(raised), Some(lowered)) => {
+                format!(" +{} -{}", raised.to_short_string(), lowered.to_short_string())
+            }
+            _ => " neutral".to_owned(),
+        };
+        let mut lines = vec![
+            Line::from(vec![
+                Span::from("Level  ").bold(),
+                highlight(Span::from(format!("{:<3}", spread.level)), row == 0),
+                Span::from(format!("{:>28}", format!("EVs {}/{MAX_TOTAL_EVS}", spread.total_evs()))).dark_gray(),
+            ]),
+            Line::from(vec![
+                Span::from("Nature ").bold(),
+                highlight(Span::from(spread.nature.to_string()), row == 1),
+                Span::from(nature).dark_gray(),
+            ]),
+            Line::from(format!(
+                "{:<6}{:>5}{:>4}{:>5}{:>6}{:>10}{:>10}",
+                "", "Base", "IV", "EV", "Stat", "Lv 50", "Lv 100",
+            ))
+            .bold(),
+        ];
+        let stats = spread.stats(base);
+        for (i, stat) in MonStat::ALL.into_iter().enumerate() {
+            let selected = row == SPREAD_ROWS + i;
+            let modifier = match spread.nature.modifier(stat) {
+                11 => "+",
+                9 => "-",
+                _ => "",
+            };
+            let range = |level| {
+                let (min, max) = stat_range(stat, base[i], level);
+                format!("{:>10}", format!("{min}-{max}"))
+            };
+            lines.push(Line::from(vec![
+                Span::from(format!("{:<6}", format!("{}{modifier}", stat.to_short_string()))),
+                Span::from(format!("{:>5}", base[i])),
+                Span::from(" "),
+                highlight(Span::from(format!("{:>3}", spread.ivs[i])), selected && column == StatsColumn::Iv),
+                Span::from(" "),
+                highlight(Span::from(format!("{:>4}", spread.evs[i])), selected && column == StatsColumn::Ev),
+                Span::from(format!("{:>6}", stats[i])).bold(),
+                Span::from(range(50)).dark_gray(),
+                Span::from(range(100)).dark_gray(),
+            ]));
+        }
+        Text::from(lines).render(block.inner(area), buf);
+        block.render(area, buf);
+    }
+}
+
+fn render_base_stat(stat: MonStat, value: u32, area: Rect, buf: &mut Buffer) {
+    let [label_area, value_area, gauge_area] = Layout::horizontal([
+        Constraint::Length(6),
+        Constraint::Length(3),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    Text::from(stat.to_short_string()).render(label_area, buf);
+    Text::from(value.to_string()).render(value_area, buf);
+    Gauge::default()
+        .gauge_style(stat.bg())
+        .label("")
+        .ratio(value as f64 / 255f64)
+        .use_unicode(true)
+        .render(gauge_area, buf)
+}
@@ -0,0 +1,9 @@
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+use crate::app::screens::pokemon::PokemonScreen;
+
+impl Widget for &PokemonScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.detail_view.render(area, buf);
+    }
+}
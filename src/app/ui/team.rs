@@ -0,0 +1,154 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+
+use crate::{
+    app::{
+        screens::{loading::LoadingState, team::{TeamPrompt, TeamRow, TeamScreen, TeamState}},
+        ui::pokedex::detail::type_span,
+    },
+    pokemon::{stats::base_stats, MonStat, MonType},
+};
+
+impl Widget for &TeamScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.read().unwrap();
+        let block = Block::bordered()
+            .title("Team")
+            .title_bottom("a in the pokedex adds, h/l to pick a slot, +/- to change, enter to edit the item, r to remove, i/e or paste to import/export, backspace to go back")
+            .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() });
+        // Import errors get a line each, up to a point
+        let summary_height = 4 + state.messages().len().clamp(1, 8) as u16;
+        let [body, summary] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(summary_height)]).areas(block.inner(area));
+        let [slots, form] = Layout::horizontal([Constraint::Length(30), Constraint::Fill(1)]).areas(body);
+
+        let slot_lines: Vec<Line> = state
+            .members()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, member)| {
+                let (name, detail) = match member {
+                    Some(member) => (
+                        member.pokemon.to_uppercase(),
+                        format!(
+                            "  @ {}, {} moves",
+                            member.item.as_deref().unwrap_or("no item").replace('-', " "),
+                            member.moves.len()
+                        ),
+                    ),
+                    None => ("-".to_owned(), String::new()),
+                };
+                let name = Span::from(format!("{} {name}", i + 1));
+                let name = if i == state.slot() { name.black().on_blue() } else { name };
+                [Line::from(name), Line::from(detail).dark_gray()]
+            })
+            .collect();
+        Paragraph::new(slot_lines).block(Block::bordered().title("Slots")).render(slots, buf);
+
+        render_form(&state, form, buf);
+        render_summary(&state, summary, buf);
+        block.render(area, buf);
+    }
+}
+
+fn render_form(state: &TeamState, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered().title(format!("Slot {}", state.slot() + 1));
+    let Some(member) = &state.members()[state.slot()] else {
+        Paragraph::new("Empty, press a on a Pokémon in the pokedex to add it here")
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(area, buf);
+        return;
+    };
+    let mon = state.pokemon(state.slot());
+    let stats = mon.loaded().map(|mon| member.spread.stats(base_stats(&mon.stats)));
+    let lines: Vec<Line> = TeamRow::all()
+        .into_iter()
+        .map(|row| {
+            let (label, value) = match row {
+                TeamRow::Pokemon => (
+                    "Pokémon".to_owned(),
+                    match mon {
+                        LoadingState::Loaded(mon) => {
+                            let types: Vec<String> = mon.types.iter().map(|t| t.type_.name.to_uppercase()).collect();
+                            format!("{} ({})", mon.name.to_uppercase(), types.join("/"))
+                        }
+                        slot => slot.to_string(),
+                    },
+                ),
+                TeamRow::Item => (
+                    "Item".to_owned(),
+                    member.item.as_deref().unwrap_or("-").replace('-', " "),
+                ),
+                TeamRow::Ability => (
+                    "Ability".to_owned(),
+                    member.ability.as_deref().unwrap_or("-").replace('-', " "),
+                ),
+                TeamRow::Level => ("Level".to_owned(), member.spread.level.to_string()),
+                TeamRow::Nature => ("Nature".to_owned(), member.spread.nature.to_string()),
+                TeamRow::Ev(stat) => {
+                    let i = MonStat::ALL.iter().position(|s| *s == stat).unwrap_or(0);
+                    let real = stats.map(|stats| format!(" ({})", stats[i])).unwrap_or_default();
+                    (format!("{} EVs", stat.to_short_string()), format!("{}{real}", member.spread.evs[i]))
+                }
+                TeamRow::Move(i) => (
+                    format!("Move {}", i + 1),
+                    member.moves.get(i).map_or("-".to_owned(), |move_| move_.replace('-', " ")),
+                ),
+            };
+            let value = match state.editing() {
+                Some(text) if row == state.selected() && state.prompt() == TeamPrompt::Item => format!("{text}_"),
+                _ => value,
+            };
+            let value = Span::from(value);
+            let value = if row == state.selected() && state.focused() { value.black().on_blue() } else { value };
+            let mut line = vec![Span::from(format!("{label:<12}")).bold(), value];
+            if let TeamRow::Move(i) = row
+                && let Some(move_type) = member.moves.get(i).and_then(|move_| state.move_type(move_))
+            {
+                line.push(Span::from(" "));
+                line.push(type_span(move_type));
+            }
+            Line::from(line)
+        })
+        .collect();
+    Paragraph::new(lines).block(block).render(area, buf);
+}
+
+fn render_summary(state: &TeamState, area: Rect, buf: &mut Buffer) {
+    let types = |types: Vec<MonType>| -> Vec<Span> {
+        if types.is_empty() {
+            return vec![Span::from("none").dark_gray()];
+        }
+        types.into_iter().flat_map(|t| [type_span(t), Span::from(" ")]).collect()
+    };
+    let mut coverage = vec![Span::from(format!("{:<12}", "Coverage")).bold()];
+    coverage.extend(types(state.coverage()));
+    let mut weak = vec![Span::from(format!("{:<12}", "Weak to")).bold()];
+    let weaknesses = state.weaknesses();
+    if weaknesses.is_empty() {
+        weak.push(Span::from("none").dark_gray());
+    }
+    for matchup in weaknesses {
+        weak.push(type_span(matchup.attacking));
+        // More members weak than resisting is a hole in the team
+        let count = Span::from(format!("×{}/{} ", matchup.weak, matchup.resist));
+        weak.push(if matchup.weak > matchup.resist { count.light_red() } else { count.dark_gray() });
+    }
+    let mut lines = vec![
+        Line::from(coverage),
+        Line::from(weak),
+        Line::from(Span::from("weak/resisting members").dark_gray()),
+    ];
+    match (state.editing(), state.prompt()) {
+        (Some(path), TeamPrompt::Import) => lines.push(Line::from(format!("Import from: {path}_")).black().on_blue()),
+        (Some(path), TeamPrompt::Export) => lines.push(Line::from(format!("Export to: {path}_")).black().on_blue()),
+        _ => lines.extend(state.messages().iter().map(|message| Line::from(message.clone()))),
+    }
+    Paragraph::new(lines).wrap(Wrap { trim: true }).render(area, buf);
+}
@@ -0,0 +1,82 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{
+    app::{screens::type_chart::TypeChartScreen, ui::pokedex::detail::type_span},
+    pokemon::effectiveness::format_multiplier,
+};
+
+impl Widget for &TypeChartScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.read().unwrap();
+        let types = state.types();
+        let attacking = types[state.attacking()];
+        let defending = types[state.defending()];
+
+        let mut title = vec![Span::from(format!("Type chart ({})", state.chart().name()))];
+        if let Some(pinned) = state.pinned() {
+            title.push(Span::from(" defending as "));
+            title.push(type_span(pinned));
+            title.push(Span::from(" + column"));
+        }
+        let block = Block::bordered()
+            .title(Line::from(title))
+            .title_bottom("enter to pin a defending type, g to change generation, backspace to go back")
+            .border_style(if state.focused() { Style::default().fg(Color::Blue) } else { Style::default() });
+        let [table_area, _, summary_area] = Layout::vertical([
+            Constraint::Length(types.len() as u16 + 1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(block.inner(area));
+
+        let header = Row::new(std::iter::once(Cell::from("ATK \\ DEF")).chain(types.iter().map(|t| {
+            let cell = Cell::from(t.to_string()[..3].to_uppercase()).style(Style::default().bg(t.bg()).fg(t.fg()));
+            if *t == defending { cell.add_modifier(Modifier::UNDERLINED) } else { cell }
+        })));
+        let rows = types.iter().map(|attacking| {
+            let label = type_span(*attacking);
+            Row::new(std::iter::once(Cell::from(label)).chain(types.iter().map(|column| {
+                let multiplier = attacking.multiplier_against_types(&state.defender(*column), state.chart());
+                multiplier_cell(multiplier)
+            })))
+        });
+        let widths = std::iter::once(Constraint::Length(9)).chain(types.iter().map(|_| Constraint::Length(3)));
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(Style::default().bg(Color::DarkGray))
+            .column_highlight_style(Style::default().bg(Color::DarkGray))
+            .cell_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut table_state = TableState::default()
+            .with_selected(Some(state.attacking()))
+            .with_selected_column(Some(state.defending() + 1));
+        StatefulWidget::render(table, table_area, buf, &mut table_state);
+
+        let defender = state.defender(defending);
+        let multiplier = attacking.multiplier_against_types(&defender, state.chart());
+        let mut summary = vec![type_span(attacking), Span::from(" against ")];
+        for (i, t) in defender.into_iter().enumerate() {
+            if i > 0 {
+                summary.push(Span::from("/"));
+            }
+            summary.push(type_span(t));
+        }
+        summary.push(Span::from(format!(" deals ×{}", format_multiplier(multiplier))));
+        Line::from(summary).render(summary_area, buf);
+        block.render(area, buf);
+    }
+}
+
+fn multiplier_cell<'a>(multiplier: f32) -> Cell<'a> {
+    match multiplier {
+        1.0 => Cell::from(""),
+        0.0 => Cell::from(" 0").bold().fg(Color::Gray),
+        m if m > 1.0 => Cell::from(format!(" {}", format_multiplier(m))).bold().fg(Color::LightGreen),
+        m => Cell::from(format!(" {}", format_multiplier(m))).fg(Color::LightRed),
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    events::{AppEvent, Event},
+    fetch::{Priority, Scheduler},
+    pokemon::{fetch_pokemon_or_species, PokemonName},
+};
+
+use super::pokedex::detail::{cancelable_fetch, LoadingState};
+
+/// One Pokémon on the compare screen, loading on its own so a slow column doesn't hold up the rest
+#[derive(Debug, Clone)]
+pub struct CompareColumn {
+    sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    /// What the column was pinned with, the loaded Pokémon can be named after a form instead
+    requested: Arc<RwLock<PokemonName>>,
+    pub state: Arc<RwLock<LoadingState>>,
+}
+
+impl CompareColumn {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler, name: PokemonName) -> Self {
+        let column = Self {
+            sender,
+            scheduler,
+            requested: Default::default(),
+            state: Default::default(),
+        };
+        column.load(name);
+        column
+    }
+
+    /// Loads `name` in place of whatever the column showed, dropping any load still running
+    pub fn load(&self, name: PokemonName) {
+        *self.requested.write().unwrap() = name.clone();
+        let cancellation_token = CancellationToken::new();
+        self.state.write().unwrap().replace(LoadingState::Loading(name.clone(), cancellation_token.clone()));
+        tokio::spawn(cancelable_fetch(cancellation_token, self.clone().fetch(name)));
+    }
+
+    /// Stops a load that is still running, for columns being removed
+    pub fn cancel(&self) {
+        if let LoadingState::Loading(_, cancellation_token) = &*self.state.read().unwrap() {
+            cancellation_token.cancel();
+        }
+    }
+
+    pub fn requested(&self) -> PokemonName {
+        self.requested.read().unwrap().clone()
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(&*self.state.read().unwrap(), LoadingState::Error(_))
+    }
+
+    async fn fetch(self, name: PokemonName) {
+        let result = fetch_pokemon_or_species(&name, &self.scheduler, Priority::Visible).await;
+        let mut state = self.state.write().unwrap();
+        // `name` is what was asked for, the Pokémon may be named after its default form instead
+        if !matches!(&*state, LoadingState::Loading(loading, _) if *loading == name) {
+            return;
+        }
+        *state = match result {
+            Ok(mon) => LoadingState::Loaded(Box::new(mon)),
+            Err(err) => LoadingState::Error(format!("{name}: {err}")),
+        };
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
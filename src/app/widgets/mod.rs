@@ -1 +1,2 @@
+pub mod compare;
 pub mod pokedex;
\ No newline at end of file
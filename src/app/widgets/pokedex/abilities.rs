@@ -4,7 +4,10 @@ use rustemon::model::pokemon::PokemonAbility;
 use tokio::sync::mpsc::UnboundedSender;
 use tui_widget_list::ListState;
 
-use crate::events::{navigation::{NavDirection, Navigation}, AppEvent, Event};
+use crate::{
+    events::{command::{Command, Commands}, navigation::{NavDirection, Navigation}, route::Route, AppEvent, Event},
+    fetch::Scheduler,
+};
 
 use super::ability::AbilityWidget;
 
@@ -14,13 +17,15 @@ use super::ability::AbilityWidget;
 #[derive(Debug, Clone)]
 pub struct AbilitiesWidget {
     sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
     pub state: Arc<RwLock<AbilitiesState>>,
 }
 
 impl AbilitiesWidget {
-    pub fn new(sender: UnboundedSender<Event>) -> Self {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
         Self {
             sender,
+            scheduler,
             state: Default::default(),
         }
     }
@@ -29,12 +34,23 @@ impl AbilitiesWidget {
         let mut state = self.state.write().unwrap();
         state.widgets.clear();
         state.list_state = ListState::default();
-        for ability in abilities {
-            state.widgets.push(AbilityWidget::new(self.sender.clone(),ability));
+        // PokeAPI leaves out the ability of slots it has no data for
+        for ability in abilities.into_iter().filter(|ability| ability.ability.is_some()) {
+            state.widgets.push(AbilityWidget::new(self.sender.clone(), self.scheduler.clone(), ability));
         }
         state.list_state.select(Some(0));
 
     }
+
+    /// Name of the ability under the cursor
+    pub fn get_selected(&self) -> Option<String> {
+        let state = self.state.read().unwrap();
+        state
+            .list_state
+            .selected
+            .and_then(|i| state.widgets.get(i))
+            .and_then(|widget| widget.state.read().unwrap().name().map(str::to_owned))
+    }
     
 
 }
@@ -82,3 +98,18 @@ impl Navigation for &AbilitiesWidget {
         
     }
 }
+
+impl Commands for &AbilitiesWidget {
+    fn handle_command(self, command: Command) -> bool {
+        let Command::Select = command else {
+            return false;
+        };
+        match self.get_selected() {
+            Some(name) => {
+                let _ = self.sender.send(Event::App(AppEvent::Push(Route::Ability(name))));
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -1,10 +1,13 @@
-use std::{default, fmt, sync::{Arc, RwLock}};
+use std::{fmt, sync::{Arc, RwLock}};
 
 use ratatui::style::Style;
-use rustemon::{error::Error, model::pokemon::{Ability, PokemonAbility}, pokemon::ability, Follow};
+use rustemon::model::{pokemon::{Ability, PokemonAbility}, resource::NamedApiResource};
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::{events::{AppEvent, Event}, pokemon::{get_client, AbilityName}};
+use crate::{
+    events::{AppEvent, Event},
+    fetch::{FetchError, Priority, Scheduler},
+};
 
 
 #[derive(Debug, Clone, Default)]
@@ -12,7 +15,7 @@ pub enum LoadingState
 {
     #[default]
     Idle,
-    Loading(PokemonAbility),
+    Loading(NamedApiResource<Ability>),
     Loaded(Ability),
     Error(String)
 }
@@ -23,7 +26,7 @@ impl fmt::Display for LoadingState
         match self
         {
             LoadingState::Idle => write!(f, "Unset"),
-            LoadingState::Loading(ability) => write!(f, "Loading {0}", ability.ability.name),
+            LoadingState::Loading(ability) => write!(f, "Loading {0}", ability.name),
             LoadingState::Loaded(ability) => write!(f, "Loaded {0}", ability.name),
             LoadingState::Error(error) => write!(f, "Error {0}", error),
         }
@@ -34,15 +37,16 @@ impl fmt::Display for LoadingState
 pub struct AbilityWidget {
     pub style: Style,
     sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
     pub state: Arc<RwLock<AbilityState>>,
 }
 
 impl AbilityWidget {
-    async fn fetch(self, ability: PokemonAbility) {
+    async fn fetch(self, ability: NamedApiResource<Ability>) {
         self.set_loading_state(LoadingState::Loading(ability.clone()));
 
         //self.set_loading_state(LoadingState::Loading);
-        match ability.ability.follow( &get_client()).await {
+        match self.scheduler.follow(&ability, Priority::Visible).await {
             Ok(ability) => self.on_load(ability),
             Err(err) => self.on_err(err),
         }
@@ -50,6 +54,9 @@ impl AbilityWidget {
 
     pub fn set_ability(&self, ability: PokemonAbility) {
         self.state.write().unwrap().hidden = ability.is_hidden;
+        let Some(ability) = ability.ability else {
+            return;
+        };
         let this = self.clone();
         tokio::spawn(this.fetch(ability));
     }
@@ -58,31 +65,25 @@ impl AbilityWidget {
         self.state.write().unwrap().loading_state = state;
     }
 
-    fn on_err(&self, err: Error) {
+    fn on_err(&self, err: FetchError) {
         self.set_loading_state(LoadingState::Error(err.to_string()));
     }
 
     fn on_load(&self, ability: Ability) {
         let mut state = self.state.write().unwrap();
-        match state.loading_state.clone()
+        if let LoadingState::Loading(pa) = &state.loading_state
+            && pa.name == ability.name
         {
-            LoadingState::Loading(pa) => 
-            {
-                if pa.ability.name == ability.name
-                {
-                    state.loading_state = LoadingState::Loaded(ability);
-                    let _ = self.sender.send(Event::App(AppEvent::Redraw));
-
-                }
-            } ,
-            _ => {}
+            state.loading_state = LoadingState::Loaded(ability);
+            let _ = self.sender.send(Event::App(AppEvent::Redraw));
         }
 
     }
 
-    pub fn new(sender: UnboundedSender<Event>, ability: PokemonAbility) -> Self {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler, ability: PokemonAbility) -> Self {
         let s = Self {
             sender: sender.clone(),
+            scheduler,
             style: Default::default(),
             state: Default::default()
         };
@@ -109,6 +110,14 @@ impl AbilityState {
     pub fn hidden(&self) -> bool {
         self.hidden
     }
+
+    pub fn name(&self) -> Option<&str> {
+        match &self.loading_state {
+            LoadingState::Idle | LoadingState::Error(_) => None,
+            LoadingState::Loading(ability) => Some(&ability.name),
+            LoadingState::Loaded(ability) => Some(&ability.name),
+        }
+    }
     
     
 }
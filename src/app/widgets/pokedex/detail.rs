@@ -1,26 +1,28 @@
 use std::{
-    default, fmt,
+    fmt,
+    future::Future,
     sync::{Arc, RwLock},
 };
 
 use ratatui::widgets::TableState;
-use rustemon::{client, error::Error, model::pokemon::{self, Pokemon}};
+use rustemon::model::{pokemon::{Pokemon, PokemonSpecies}, resource::NamedApiResource};
 use tokio::{select, sync::mpsc::UnboundedSender};
 
-use crate::{events::navigation::NavDirection, pokemon::get_client};
+use crate::events::navigation::NavDirection;
 use crate::{
-    events::{AppEvent, Event, navigation::Navigation},
-    pokemon::PokemonName,
+    events::{AppEvent, Event, TeamAddition, command::{Command, Commands}, navigation::Navigation, route::Route},
+    fetch::{FetchError, Priority, Scheduler},
+    pokemon::{PokemonName, effectiveness::TypeChart},
 };
 use tokio_util::sync::CancellationToken;
-use super::{abilities::AbilitiesWidget, moves::MovesWidget};
+use super::{abilities::AbilitiesWidget, evolution::EvolutionWidget, moves::MovesWidget, stats::StatsWidget};
 
 #[derive(Debug, Clone, Default)]
 pub enum LoadingState {
     #[default]
     Idle,
     Loading(PokemonName,CancellationToken),
-    Loaded(Pokemon),
+    Loaded(Box<Pokemon>),
     Error(String),
 }
 
@@ -35,81 +37,177 @@ impl fmt::Display for LoadingState {
     }
 }
 
+impl LoadingState {
+    /// Replaces the state, cancelling the load it replaces if that is still running
+    pub fn replace(&mut self, loading_state: LoadingState) {
+        if let LoadingState::Loading(_, cancellation_token) = self {
+            cancellation_token.cancel();
+        }
+        *self = loading_state;
+    }
+}
+
+/// Runs `fetch` until it finishes or `token` is cancelled, for loads a [`LoadingState`] replaces
+pub async fn cancelable_fetch(token: CancellationToken, fetch: impl Future<Output = ()>) {
+    select! {
+        _ = token.cancelled() => {}
+        _ = fetch => {}
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum SpeciesLoadingState {
+    #[default]
+    Loading,
+    Loaded(Box<PokemonSpecies>),
+    Error(String),
+}
+
+impl fmt::Display for SpeciesLoadingState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpeciesLoadingState::Loading => write!(f, "Loading species"),
+            SpeciesLoadingState::Loaded(species) => write!(f, "Loaded {0}", species.name),
+            SpeciesLoadingState::Error(error) => write!(f, "Error {0}", error),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DetailsWidget {
     sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    pub stats: StatsWidget,
     pub abilities: AbilitiesWidget,
+    pub evolution: EvolutionWidget,
     pub moves : MovesWidget,
     pub state: Arc<RwLock<DetailsState>>,
 }
 
 impl DetailsWidget {
     async fn fetch_mon(self, name: String) {
-        let rustemon_client = get_client();
-
         //self.set_loading_state(LoadingState::Loading);
-        match rustemon::pokemon::pokemon::get_by_name(name.as_str(), &rustemon_client).await {
+        match self.scheduler.pokemon(&name, Priority::Visible).await {
             Ok(mon) => self.on_load(mon),
             Err(err) => self.on_err(err),
         }
     }
 
-    async fn cancelable_fetch(self, name: String , token: CancellationToken)
-    {
-        
-        // self.set_loading_state(LoadingState::Loading(name.clone(), token));
-        select! {
-            _= token.cancelled()=> {}
-            _= self.fetch_mon(name)=>{}
-
+    /// Species names aren't always Pokémon names (deoxys only exists as deoxys-normal and friends),
+    /// so the species is fetched first and its default variety is loaded
+    async fn fetch_default_variety(self, name: String, token: CancellationToken) {
+        match self.scheduler.species(&name, Priority::Visible).await {
+            Ok(species) => {
+                let variety = species
+                    .varieties
+                    .iter()
+                    .find(|variety| variety.is_default)
+                    .or(species.varieties.first())
+                    .map_or(name, |variety| variety.pokemon.name.clone());
+                {
+                    let mut state = self.state.write().unwrap();
+                    if token.is_cancelled() {
+                        return;
+                    }
+                    state.loading_state = LoadingState::Loading(variety.clone(), token);
+                    self.evolution.set_species(&species);
+                    state.species = SpeciesLoadingState::Loaded(Box::new(species));
+                }
+                self.fetch_mon(variety).await
+            }
+            Err(err) => self.on_err(err),
         }
-
     }
 
     pub fn set_mon(&self, name: PokemonName) {
         let this = self.clone();
         let cancellation_token = CancellationToken::new();
         self.set_loading_state(LoadingState::Loading(name.clone(), cancellation_token.clone()));
-        tokio::spawn(this.cancelable_fetch(name,cancellation_token.clone()));
+        tokio::spawn(cancelable_fetch(cancellation_token, this.fetch_mon(name)));
     }
 
-    fn set_loading_state(&self, loading_state: LoadingState) {
-        let mut  state = self.state.write().unwrap();
-        match state.loading_state.clone() {
-            LoadingState::Loading(name, cancellation_token) => 
-            {
-                cancellation_token.cancel();
-                state.loading_state = loading_state;
-            },
-            _ => {state.loading_state = loading_state},
+    /// Loads the default form of a species, use [`Self::set_mon`] for a specific form
+    pub fn set_species(&self, name: String) {
+        let this = self.clone();
+        let cancellation_token = CancellationToken::new();
+        self.set_loading_state(LoadingState::Loading(name.clone(), cancellation_token.clone()));
+        tokio::spawn(cancelable_fetch(
+            cancellation_token.clone(),
+            this.fetch_default_variety(name, cancellation_token),
+        ));
+    }
+
+    /// Switches to the next variety of the loaded species, wrapping around
+    fn cycle_form(&self) -> bool {
+        let state = self.state.read().unwrap();
+        let (LoadingState::Loaded(mon), SpeciesLoadingState::Loaded(species)) = (&state.loading_state, &state.species)
+        else {
+            return false;
         };
+        if species.varieties.len() < 2 {
+            return false;
+        }
+        let current = species.varieties.iter().position(|variety| variety.pokemon.name == mon.name);
+        let next = current.map_or(0, |i| (i + 1) % species.varieties.len());
+        let name = species.varieties[next].pokemon.name.clone();
+        drop(state);
+        self.set_mon(name);
+        true
+    }
+
+    fn set_loading_state(&self, loading_state: LoadingState) {
+        self.state.write().unwrap().loading_state.replace(loading_state);
     }
 
-    fn on_err(&self, err: Error) {
+    fn on_err(&self, err: FetchError) {
         self.set_loading_state(LoadingState::Error(err.to_string()));
     }
 
     fn on_load(&self, mon: Pokemon) {
         let mut state = self.state.write().unwrap();
-        match state.loading_state.clone() {
-            LoadingState::Loading(name,_) => {
-                if name == mon.name {
-                    self.abilities.set_abilities(mon.abilities.clone());
-                    self.moves.set_moves(mon.moves.clone());
-                    state.loading_state = LoadingState::Loaded(mon);
-                    let _ = self.sender.send(Event::App(AppEvent::Redraw));
-                }
+        if let LoadingState::Loading(name, _) = &state.loading_state
+            && *name == mon.name
+        {
+            self.stats.set_stats(&mon.stats);
+            self.abilities.set_abilities(mon.abilities.clone());
+            self.moves.set_moves(mon.moves.clone());
+            // Switching forms keeps the species
+            if !matches!(&state.species, SpeciesLoadingState::Loaded(species) if species.name == mon.species.name) {
+                state.species = SpeciesLoadingState::Loading;
+                tokio::spawn(self.clone().fetch_species(mon.species.clone()));
             }
-            _ => {}
+            state.loading_state = LoadingState::Loaded(Box::new(mon));
+            let _ = self.sender.send(Event::App(AppEvent::Redraw));
         }
     }
 
+    async fn fetch_species(self, species: NamedApiResource<PokemonSpecies>) {
+        let result = self.scheduler.follow(&species, Priority::Visible).await;
+        let mut state = self.state.write().unwrap();
+        let LoadingState::Loaded(mon) = &state.loading_state else {
+            return;
+        };
+        if mon.species.name != species.name {
+            return;
+        }
+        state.species = match result {
+            Ok(species) => {
+                self.evolution.set_species(&species);
+                SpeciesLoadingState::Loaded(Box::new(species))
+            }
+            Err(err) => SpeciesLoadingState::Error(err.to_string()),
+        };
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
 
-    pub fn new(sender: UnboundedSender<Event>) -> Self {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
         Self {
             sender: sender.clone(),
-            abilities: AbilitiesWidget::new(sender.clone()),
-            moves: MovesWidget::new(sender.clone()),
+            stats: StatsWidget::new(sender.clone()),
+            abilities: AbilitiesWidget::new(sender.clone(), scheduler.clone()),
+            evolution: EvolutionWidget::new(sender.clone(), scheduler.clone()),
+            moves: MovesWidget::new(sender.clone(), scheduler.clone()),
+            scheduler,
             state: Default::default(),
         }
     }
@@ -117,8 +215,11 @@ impl DetailsWidget {
 
 #[derive(Debug, Clone, Copy, Default)]
 enum DetailsFocus {
+    /// Only reachable while the stat calculator is open
+    Stats,
     #[default]
     Abilities,
+    Evolution,
     Moves,
 }
 
@@ -127,6 +228,9 @@ pub struct DetailsState {
     focused: bool,
     current_focus: DetailsFocus,
     loading_state: LoadingState,
+    species: SpeciesLoadingState,
+    /// Generation the matchups are shown for
+    type_chart: TypeChart,
     pub ability_table_state: TableState,
 }
 
@@ -138,16 +242,38 @@ impl DetailsState {
     pub fn loading_state(&self) -> &LoadingState {
         &self.loading_state
     }
+
+    pub fn species(&self) -> &SpeciesLoadingState {
+        &self.species
+    }
+
+    pub fn type_chart(&self) -> TypeChart {
+        self.type_chart
+    }
 }
 
 impl Navigation for &DetailsWidget {
     fn handle_navigation_input(self, direction: NavDirection) -> bool {
         let mut state = self.state.write().unwrap();
         match (state.current_focus, direction) {
+            (DetailsFocus::Stats, direction) if self.stats.handle_navigation_input(direction) => true,
+            (DetailsFocus::Stats, NavDirection::Tab) => {
+                self.stats.unfocus();
+                state.current_focus = DetailsFocus::Abilities;
+                self.abilities.focus();
+                true
+            }
+            (DetailsFocus::Stats, _) => false,
+            (DetailsFocus::Abilities, NavDirection::BackTab) if self.stats.state.read().unwrap().calculator() => {
+                self.abilities.unfocus();
+                state.current_focus = DetailsFocus::Stats;
+                self.stats.focus();
+                true
+            }
             (DetailsFocus::Abilities, NavDirection::Tab) => {
                 self.abilities.unfocus();
-                state.current_focus = DetailsFocus::Moves;
-                self.moves.focus();
+                state.current_focus = DetailsFocus::Evolution;
+                self.evolution.focus();
 
                 true
             }
@@ -155,12 +281,25 @@ impl Navigation for &DetailsWidget {
             (DetailsFocus::Abilities, direction) => {
                 self.abilities.handle_navigation_input(direction)
             }
+            (DetailsFocus::Evolution, NavDirection::Tab) => {
+                self.evolution.unfocus();
+                state.current_focus = DetailsFocus::Moves;
+                self.moves.focus();
+                true
+            }
+            (DetailsFocus::Evolution, NavDirection::BackTab) => {
+                self.evolution.unfocus();
+                state.current_focus = DetailsFocus::Abilities;
+                self.abilities.focus();
+                true
+            }
+            (DetailsFocus::Evolution, direction) => self.evolution.handle_navigation_input(direction),
             (DetailsFocus::Moves, NavDirection::Up | NavDirection::Down) => self.moves.handle_navigation_input(direction), //todo
             (DetailsFocus::Moves, NavDirection::Tab) => false,
             (DetailsFocus::Moves, NavDirection::BackTab) => {
                 self.moves.unfocus();
-                state.current_focus = DetailsFocus::Abilities;
-                self.abilities.focus();
+                state.current_focus = DetailsFocus::Evolution;
+                self.evolution.focus();
                 true
             }
         }
@@ -174,8 +313,107 @@ impl Navigation for &DetailsWidget {
     }
 
     fn unfocus(self) {
-        self.abilities.unfocus();
-        self.state.write().unwrap().focused= false;
+        let mut state = self.state.write().unwrap();
+        match state.current_focus {
+            DetailsFocus::Stats => self.stats.unfocus(),
+            DetailsFocus::Abilities => self.abilities.unfocus(),
+            DetailsFocus::Evolution => self.evolution.unfocus(),
+            DetailsFocus::Moves => self.moves.unfocus(),
+        }
+        state.focused= false;
         let _ = self.sender.send(Event::App(AppEvent::Redraw));
     }
 }
+
+impl Commands for &DetailsWidget {
+    fn handle_command(self, command: Command) -> bool {
+        match command {
+            Command::CycleTypeChart => {
+                let mut state = self.state.write().unwrap();
+                state.type_chart = state.type_chart.next();
+                let _ = self.sender.send(Event::App(AppEvent::Redraw));
+                true
+            }
+            Command::CycleVersionGroup => {
+                self.moves.cycle_version_group();
+                true
+            }
+            Command::CycleForm => self.cycle_form(),
+            Command::DamageCalc => {
+                let state = self.state.read().unwrap();
+                let LoadingState::Loaded(mon) = &state.loading_state else {
+                    return false;
+                };
+                let move_ = match (state.focused, state.current_focus) {
+                    (true, DetailsFocus::Moves) => self.moves.get_selected(),
+                    _ => None,
+                };
+                let route = Route::DamageCalc { attacker: Some(mon.name.clone()), move_ };
+                let _ = self.sender.send(Event::App(AppEvent::Push(route)));
+                true
+            }
+            Command::Compare => {
+                let state = self.state.read().unwrap();
+                let LoadingState::Loaded(mon) = &state.loading_state else {
+                    return false;
+                };
+                let _ = self.sender.send(Event::App(AppEvent::Push(Route::Compare(Some(mon.name.clone())))));
+                true
+            }
+            Command::AddToTeam => {
+                let state = self.state.read().unwrap();
+                let LoadingState::Loaded(mon) = &state.loading_state else {
+                    return false;
+                };
+                let addition = match (state.focused, state.current_focus) {
+                    (true, DetailsFocus::Abilities) => self.abilities.get_selected().map(TeamAddition::Ability),
+                    (true, DetailsFocus::Moves) => self.moves.get_selected().map(TeamAddition::Move),
+                    _ => None,
+                };
+                let addition = addition.unwrap_or_else(|| TeamAddition::Pokemon(mon.name.clone()));
+                let _ = self.sender.send(Event::App(AppEvent::AddToTeam(addition)));
+                true
+            }
+            Command::ToggleStatCalculator => {
+                let mut state = self.state.write().unwrap();
+                let open = self.stats.toggle_calculator();
+                if !state.focused {
+                    return true;
+                }
+                // Opening the calculator jumps to it, closing it leaves nothing to focus there
+                match (open, state.current_focus) {
+                    (true, DetailsFocus::Abilities) => self.abilities.unfocus(),
+                    (true, DetailsFocus::Evolution) => self.evolution.unfocus(),
+                    (true, DetailsFocus::Moves) => self.moves.unfocus(),
+                    (false, DetailsFocus::Stats) => {
+                        self.stats.unfocus();
+                        state.current_focus = DetailsFocus::Abilities;
+                        self.abilities.focus();
+                        return true;
+                    }
+                    _ => return true,
+                }
+                state.current_focus = DetailsFocus::Stats;
+                self.stats.focus();
+                true
+            }
+            _ => {
+                let state = self.state.read().unwrap();
+                match (state.focused, state.current_focus) {
+                    (true, DetailsFocus::Stats) => self.stats.handle_command(command),
+                    (true, DetailsFocus::Abilities) => self.abilities.handle_command(command),
+                    (true, DetailsFocus::Evolution) => match (command, self.evolution.get_selected()) {
+                        (Command::Select, Some(species)) => {
+                            drop(state);
+                            self.set_species(species);
+                            true
+                        }
+                        _ => false,
+                    },
+                    (true, DetailsFocus::Moves) => self.moves.handle_command(command),
+                    _ => false,
+                }
+            }
+        }
+    }
+}
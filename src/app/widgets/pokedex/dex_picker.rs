@@ -0,0 +1,193 @@
+use std::{fmt, sync::{Arc, RwLock}};
+
+use futures::future::try_join_all;
+use rustemon::model::locations::Region;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    events::{navigation::{NavDirection, Navigation}, AppEvent, Event},
+    fetch::{FetchError, Priority, Scheduler},
+};
+
+/// Label for dexes that don't belong to a region, like the national dex
+const NO_REGION: &str = "Other";
+
+#[derive(Debug, Clone)]
+pub struct DexGroup {
+    pub region: String,
+    pub dexes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum LoadingState
+{
+    #[default]
+    Idle,
+    Loading,
+    Loaded(Vec<DexGroup>),
+    Error(String)
+}
+
+impl fmt::Display for LoadingState
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            LoadingState::Idle => write!(f, "Unset"),
+            LoadingState::Loading => write!(f, "Loading pokédexes"),
+            LoadingState::Loaded(groups) => write!(f, "Loaded {0} regions", groups.len()),
+            LoadingState::Error(error) => write!(f, "Error {0}", error),
+        }
+    }
+}
+
+/// Popup listing every pokédex from PokeAPI, grouped by region
+#[derive(Debug, Clone)]
+pub struct DexPickerWidget {
+    sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    pub state: Arc<RwLock<DexPickerState>>,
+}
+
+impl DexPickerWidget {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
+        Self {
+            sender,
+            scheduler,
+            state: Default::default(),
+        }
+    }
+
+    /// Opens the popup with `current` selected, the list is only fetched the first time
+    pub fn open(&self, current: &str) {
+        let mut state = self.state.write().unwrap();
+        state.open = true;
+        state.current = current.to_owned();
+        match state.loading_state {
+            LoadingState::Idle | LoadingState::Error(_) => {
+                state.loading_state = LoadingState::Loading;
+                tokio::spawn(self.clone().fetch());
+            }
+            LoadingState::Loading => {}
+            LoadingState::Loaded(_) => state.select_current(),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    pub fn close(&self) {
+        self.state.write().unwrap().open = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state.read().unwrap().open
+    }
+
+    pub fn get_selected(&self) -> Option<String> {
+        let state = self.state.read().unwrap();
+        state.dexes().nth(state.selected).map(str::to_owned)
+    }
+
+    async fn fetch(self) {
+        match self.fetch_groups().await {
+            Ok(groups) => {
+                let mut state = self.state.write().unwrap();
+                state.loading_state = LoadingState::Loaded(groups);
+                state.select_current();
+            }
+            Err(err) => self.state.write().unwrap().loading_state = LoadingState::Error(err.to_string()),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    async fn fetch_groups(&self) -> Result<Vec<DexGroup>, FetchError> {
+        let regions = self.scheduler.regions(Priority::Visible).await?;
+        let regions: Vec<Region> =
+            try_join_all(regions.iter().map(|region| self.scheduler.follow(region, Priority::Visible))).await?;
+        let all_dexes = self.scheduler.pokedexes(Priority::Visible).await?;
+
+        let regionless: Vec<String> = all_dexes
+            .into_iter()
+            .map(|dex| dex.name)
+            .filter(|name| {
+                !regions
+                    .iter()
+                    .any(|region| region.pokedexes.iter().any(|dex| dex.name == *name))
+            })
+            .collect();
+        let mut groups = vec![DexGroup {
+            region: NO_REGION.to_owned(),
+            dexes: regionless,
+        }];
+        groups.extend(regions.into_iter().map(|region| DexGroup {
+            region: region
+                .names
+                .iter()
+                .find(|name| name.language.name == "en")
+                .map(|name| name.name.clone())
+                .unwrap_or(region.name),
+            dexes: region.pokedexes.into_iter().map(|dex| dex.name).collect(),
+        }));
+        groups.retain(|group| !group.dexes.is_empty());
+        Ok(groups)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DexPickerState {
+    open: bool,
+    /// The dex the screen is showing
+    current: String,
+    /// Index into the flattened list of dexes, headers aren't selectable
+    selected: usize,
+    loading_state: LoadingState,
+}
+
+impl DexPickerState {
+    pub fn loading_state(&self) -> &LoadingState {
+        &self.loading_state
+    }
+
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    fn dexes(&self) -> impl Iterator<Item = &str> {
+        let groups = match &self.loading_state {
+            LoadingState::Loaded(groups) => groups.as_slice(),
+            _ => &[],
+        };
+        groups.iter().flat_map(|group| group.dexes.iter().map(String::as_str))
+    }
+
+    fn select_current(&mut self) {
+        let selected = self.dexes().position(|dex| dex == self.current);
+        self.selected = selected.unwrap_or(0);
+    }
+}
+
+impl Navigation for &DexPickerWidget {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        let count = state.dexes().count();
+        match direction {
+            NavDirection::Up => state.selected = state.selected.saturating_sub(1),
+            NavDirection::Down => state.selected = usize::min(state.selected + 1, count.saturating_sub(1)),
+            _ => return false,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
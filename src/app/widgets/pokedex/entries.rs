@@ -1,10 +1,46 @@
-use std::sync::{Arc, RwLock};
+use std::{cmp::Ordering, collections::{HashMap, HashSet}, sync::{Arc, RwLock}};
 
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 use ratatui::widgets::TableState;
 use rustemon::model::games::PokemonEntry;
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::{events::{navigation::{NavDirection, Navigation}, AppEvent, Event}, pokemon::PokemonName};
+use crate::{collection::{Collection, Mark, Marks}, events::{input::{TextEditing, TextInput}, navigation::{NavDirection, Navigation}, AppEvent, Event}, pokemon::{filter::{IndexEntry, Query, StatKey}, PokemonName}};
+
+/// Score given to an exact entry number hit, so `25` always puts #0025 first
+const EXACT_NUMBER_SCORE: i64 = i64::MAX;
+
+/// What the entries are ordered by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortColumn {
+    /// Dex order, or best match first while searching by name
+    #[default]
+    Number,
+    Name,
+    Stat(StatKey),
+}
+
+impl SortColumn {
+    pub fn all() -> Vec<SortColumn> {
+        let mut columns = vec![SortColumn::Number, SortColumn::Name];
+        columns.extend(StatKey::ALL.map(SortColumn::Stat));
+        columns
+    }
+
+    pub fn next(self) -> Self {
+        let all = Self::all();
+        let index = all.iter().position(|column| *column == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    pub fn to_short_string(self) -> String {
+        match self {
+            SortColumn::Number => "#".to_owned(),
+            SortColumn::Name => "name".to_owned(),
+            SortColumn::Stat(key) => key.to_short_string(),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct EntriesWidget {
@@ -14,41 +50,101 @@ pub struct EntriesWidget {
 
 impl EntriesWidget {
 
-    fn select(&self, index: Option<usize>) -> Option<PokemonName> {
+    pub fn get_selectected(&self) -> Option<PokemonName> {
+        self.state.read().unwrap().selected_name()
+    }
+
+    /// Replaces the entries, keeping the selected species if it is in the new list
+    pub fn set_entries(&self, dex: &str, entries: &[PokemonEntry]) -> Option<PokemonName>{
         let mut state = self.state.write().unwrap();
-        state.table_state.select(index);
-        match state.table_state.selected() {
-            Some(index) => match state.entries.get(index) {
-                Some(entry) => return Some(entry.pokemon_species.name.clone()),
-                None => return None,
-            },
-            None => return None,
-        }
+        let selected = state.selected_name();
+        state.dex = dex.to_owned();
+        state.entries = entries.to_vec();
+        state.apply_filter(selected);
+        state.selected_name()
     }
 
-    pub fn get_selectected(&self) -> Option<PokemonName> {
-        let state = self.state.read().unwrap();
-        match state.table_state.selected() {
-            Some(index) => match state.entries.get(index) {
-                Some(entry) => return Some(entry.pokemon_species.name.clone()),
-                None => return None,
-            },
-            None => return None,
-        }
+    /// Marks or unmarks the selected species in the current dex
+    pub fn toggle_mark(&self, mark: Mark) -> bool {
+        let mut state = self.state.write().unwrap();
+        let Some(name) = state.selected_name() else {
+            return false;
+        };
+        let dex = state.dex.clone();
+        state.save_error = state.collection.toggle(&dex, &name, mark).err().map(|err| err.to_string());
+        // A species that was just caught drops out of the missing list
+        state.apply_filter_keeping_row();
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    /// Species in the list the filters, sorting or extra columns need data for, empty when nothing
+    /// being shown needs the index. Species that failed before are only in it with `retry_failed`
+    pub fn missing_from_index(&self, retry_failed: bool) -> Vec<PokemonName> {
+        self.state.write().unwrap().missing_from_index(retry_failed)
     }
 
-    pub fn set_entries(&self, entries: &Vec<PokemonEntry>) -> Option<PokemonName>{
-        {
-            let mut state = self.state.write().unwrap();
-            state.entries = entries.clone();
+    /// Adds newly indexed species and filters again, returns the selected species like [`Self::set_entries`]
+    pub fn add_to_index(&self, entries: Vec<(PokemonName, Result<IndexEntry, String>)>) -> Option<PokemonName> {
+        let mut state = self.state.write().unwrap();
+        for (name, entry) in entries {
+            match entry {
+                Ok(entry) => {
+                    state.index.insert(name, entry);
+                }
+                Err(_) => {
+                    state.index_failed.insert(name);
+                }
+            }
         }
-        self.select(Some(0))
+        let selected = state.selected_name();
+        state.apply_filter(selected);
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        state.selected_name()
+    }
+
+    pub fn set_indexing(&self, indexing: bool) {
+        self.state.write().unwrap().indexing = indexing;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    /// Moves on to the next sort column, starting from the biggest stats first
+    pub fn cycle_sort(&self) {
+        let mut state = self.state.write().unwrap();
+        let selected = state.selected_name();
+        state.sort = state.sort.next();
+        state.descending = matches!(state.sort, SortColumn::Stat(_));
+        state.apply_filter(selected);
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
     }
 
-    pub fn new(sender: UnboundedSender<Event>) -> Self {
+    pub fn reverse_sort(&self) {
+        let mut state = self.state.write().unwrap();
+        let selected = state.selected_name();
+        state.descending = !state.descending;
+        state.apply_filter(selected);
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    /// Shows or hides the types and base stats columns
+    pub fn toggle_columns(&self) {
+        let mut state = self.state.write().unwrap();
+        state.wide = !state.wide;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    pub fn toggle_missing_only(&self) -> bool {
+        let mut state = self.state.write().unwrap();
+        state.missing_only = !state.missing_only;
+        state.apply_filter_keeping_row();
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    pub fn new(sender: UnboundedSender<Event>, collection: Collection) -> Self {
         Self {
             sender,
-            state: Default::default(),
+            state: Arc::new(RwLock::new(EntriesState { collection, ..Default::default() })),
         }
     }
 
@@ -58,7 +154,31 @@ impl EntriesWidget {
 pub struct EntriesState {
     focused : bool,
     entries: Vec<PokemonEntry>,
+    /// Indices into `entries` that match `query`, best match first
+    visible: Vec<usize>,
+    query: String,
+    editing: bool,
     table_state: TableState,
+    /// Name of the dex the entries come from, marks are kept per dex
+    dex: String,
+    collection: Collection,
+    /// Hide caught species, for working through a living dex
+    missing_only: bool,
+    /// Why the last mark couldn't be saved
+    save_error: Option<String>,
+    /// The last query that parsed, kept while the one being typed is incomplete
+    parsed: Query,
+    query_error: Option<String>,
+    /// Data for the structured filters by species, kept across dexes
+    index: HashMap<PokemonName, IndexEntry>,
+    /// Species that couldn't be indexed, they are left out of filtered lists until indexing is
+    /// started again
+    index_failed: HashSet<PokemonName>,
+    indexing: bool,
+    sort: SortColumn,
+    descending: bool,
+    /// Show the types and base stats next to the names
+    wide: bool,
 }
 
 impl EntriesState {
@@ -66,13 +186,197 @@ impl EntriesState {
         &self.entries
     }
 
+    pub fn visible_entries(&self) -> impl Iterator<Item = &PokemonEntry> {
+        self.visible.iter().map(|index| &self.entries[*index])
+    }
+
     pub fn table_state(&mut self) -> &mut TableState {
         &mut self.table_state
     }
-    
+
     pub fn focused(&self) -> bool {
         self.focused
     }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn editing(&self) -> bool {
+        self.editing
+    }
+
+    pub fn marks(&self, species: &str) -> Marks {
+        self.collection.marks(&self.dex, species)
+    }
+
+    /// Caught species in the current dex
+    pub fn completion(&self) -> usize {
+        self.entries.iter().filter(|entry| self.marks(&entry.pokemon_species.name).caught).count()
+    }
+
+    pub fn query_error(&self) -> Option<&str> {
+        self.query_error.as_deref()
+    }
+
+    /// How many of the entries are indexed and failed to index, `None` when nothing needs the index
+    pub fn index_progress(&self) -> Option<(usize, usize)> {
+        if !self.needs_index() {
+            return None;
+        }
+        let indexed = self.entries.iter().filter(|entry| self.index.contains_key(&entry.pokemon_species.name)).count();
+        let failed = self.entries.iter().filter(|entry| self.index_failed.contains(&entry.pokemon_species.name)).count();
+        Some((indexed, failed))
+    }
+
+    pub fn indexing(&self) -> bool {
+        self.indexing
+    }
+
+    /// Filled in by the background loader, `None` until the species has been loaded
+    pub fn index_entry(&self, species: &str) -> Option<&IndexEntry> {
+        self.index.get(species)
+    }
+
+    pub fn sort(&self) -> (SortColumn, bool) {
+        (self.sort, self.descending)
+    }
+
+    pub fn wide(&self) -> bool {
+        self.wide
+    }
+
+    fn needs_index(&self) -> bool {
+        !self.parsed.conditions.is_empty() || self.wide || matches!(self.sort, SortColumn::Stat(_))
+    }
+
+    /// Species that aren't loaded yet go last whichever way the stats are sorted
+    fn compare(&self, a: &PokemonEntry, b: &PokemonEntry) -> Ordering {
+        let direction = |ordering: Ordering| if self.descending { ordering.reverse() } else { ordering };
+        let ordering = match self.sort {
+            SortColumn::Number => direction(a.entry_number.cmp(&b.entry_number)),
+            SortColumn::Name => direction(a.pokemon_species.name.cmp(&b.pokemon_species.name)),
+            SortColumn::Stat(key) => {
+                let value = |entry: &PokemonEntry| self.index.get(&entry.pokemon_species.name).map(|entry| entry.stat(key));
+                match (value(a), value(b)) {
+                    (Some(a), Some(b)) => direction(a.cmp(&b)),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }
+        };
+        ordering.then(a.entry_number.cmp(&b.entry_number))
+    }
+
+    pub fn missing_only(&self) -> bool {
+        self.missing_only
+    }
+
+    pub fn save_error(&self) -> Option<&str> {
+        self.save_error.as_deref()
+    }
+
+    fn missing_from_index(&mut self, retry_failed: bool) -> Vec<PokemonName> {
+        if !self.needs_index() {
+            return Vec::new();
+        }
+        if retry_failed {
+            self.index_failed.clear();
+        }
+        self.entries
+            .iter()
+            .map(|entry| &entry.pokemon_species.name)
+            .filter(|name| !self.index.contains_key(*name) && !self.index_failed.contains(*name))
+            .cloned()
+            .collect()
+    }
+
+    fn selected_name(&self) -> Option<PokemonName> {
+        let index = self.visible.get(self.table_state.selected()?)?;
+        Some(self.entries[*index].pokemon_species.name.clone())
+    }
+
+    /// Rebuilds `visible` from `query`, keeping `selected` if it still matches. Species that aren't
+    /// indexed yet are hidden while there are structured filters
+    fn apply_filter(&mut self, selected: Option<PokemonName>) {
+        match self.query.parse::<Query>() {
+            Ok(parsed) => {
+                self.parsed = parsed;
+                self.query_error = None;
+            }
+            Err(err) => self.query_error = Some(err.to_string()),
+        }
+        self.visible = filter_entries(&self.entries, &self.parsed.name);
+        if !self.parsed.conditions.is_empty() {
+            let (entries, index, parsed) = (&self.entries, &self.index, &self.parsed);
+            self.visible
+                .retain(|i| index.get(&entries[*i].pokemon_species.name).is_some_and(|entry| parsed.matches(entry)));
+        }
+        // Dex order is how the list already is, searching by name keeps the best matches first
+        if self.sort != SortColumn::Number || self.descending {
+            let mut visible = std::mem::take(&mut self.visible);
+            visible.sort_by(|a, b| self.compare(&self.entries[*a], &self.entries[*b]));
+            self.visible = visible;
+        }
+        if self.missing_only {
+            let (entries, collection) = (&self.entries, &self.collection);
+            self.visible.retain(|i| !collection.marks(&self.dex, &entries[*i].pokemon_species.name).caught);
+        }
+        let index = selected
+            .and_then(|name| {
+                self.visible
+                    .iter()
+                    .position(|i| self.entries[*i].pokemon_species.name == name)
+            })
+            .unwrap_or(0);
+        self.table_state.select(if self.visible.is_empty() { None } else { Some(index) });
+    }
+
+    /// Filters again for when the selected species may be hidden, the cursor then goes to the
+    /// next species still shown instead of back to the top
+    fn apply_filter_keeping_row(&mut self) {
+        let following = self.table_state.selected().and_then(|row| self.visible.get(row..)).unwrap_or_default().to_vec();
+        self.apply_filter(None);
+        let row = following
+            .iter()
+            .find_map(|index| self.visible.iter().position(|i| i == index))
+            .or_else(|| self.visible.len().checked_sub(1));
+        self.table_state.select(row);
+    }
+}
+
+/// Fuzzy matches `query` against species names and entry numbers
+fn filter_entries(entries: &[PokemonEntry], query: &str) -> Vec<usize> {
+    let query = query.trim().trim_start_matches('#');
+    if query.is_empty() {
+        return (0..entries.len()).collect();
+    }
+    let matcher = SkimMatcherV2::default();
+    let number_query = query.trim_start_matches('0');
+    let numeric = !number_query.is_empty() && number_query.chars().all(|c| c.is_ascii_digit());
+    let mut scored: Vec<(i64, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let number_score = if numeric {
+                let number = entry.entry_number.to_string();
+                if number == number_query {
+                    Some(EXACT_NUMBER_SCORE)
+                } else {
+                    number.starts_with(number_query).then_some(EXACT_NUMBER_SCORE - 1)
+                }
+            } else {
+                None
+            };
+            let name_score = matcher.fuzzy_match(&entry.pokemon_species.name, query);
+            number_score.max(name_score).map(|score| (score, index))
+        })
+        .collect();
+    scored.sort_by(|(a_score, a_index), (b_score, b_index)| {
+        b_score.cmp(a_score).then(a_index.cmp(b_index))
+    });
+    scored.into_iter().map(|(_, index)| index).collect()
 }
 
 
@@ -82,39 +386,167 @@ impl Navigation for &EntriesWidget
     {
         match direction {
             NavDirection::Up => {
-                let this = &self;
-                let mut state = this.state.write().unwrap();
+                let mut state = self.state.write().unwrap();
                 state.table_state.scroll_up_by(1);
-                let _ = this.sender.send(Event::App(AppEvent::Redraw));
+                let _ = self.sender.send(Event::App(AppEvent::Redraw));
                 true
             },
             NavDirection::Down => {
-                let this = &self;
-                let mut state = this.state.write().unwrap();
+                let mut state = self.state.write().unwrap();
                 state.table_state.scroll_down_by(1);
-                let _ = this.sender.send(Event::App(AppEvent::Redraw));
+                let _ = self.sender.send(Event::App(AppEvent::Redraw));
                 true
             },
             _ => false,
         }
 
     }
-    
+
     fn focus(self) {
         self.state.write().unwrap().focused= true;
         let _ = self.sender.send(Event::App(AppEvent::Redraw));
 
     }
-    
+
     fn unfocus(self) {
         self.state.write().unwrap().focused= false;
         let _ = self.sender.send(Event::App(AppEvent::Redraw));
 
     }
-    
 
-    
 
+
+
+}
+
+impl TextEditing for &EntriesWidget
+{
+    fn editing(self) -> bool {
+        self.state.read().unwrap().editing
+    }
+
+    fn start_editing(self) -> bool {
+        self.state.write().unwrap().editing = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn handle_text_input(self, input: TextInput) -> bool {
+        let mut state = self.state.write().unwrap();
+        if !state.editing {
+            return false;
+        }
+        let selected = state.selected_name();
+        match input {
+            TextInput::Char(c) => state.query.push(c),
+            TextInput::Backspace => {
+                state.query.pop();
+            }
+            TextInput::Submit => state.editing = false,
+            TextInput::Cancel => {
+                state.editing = false;
+                state.query.clear();
+            }
+        }
+        state.apply_filter(selected);
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(entries: &[(i64, &str)]) -> Vec<PokemonEntry> {
+        entries
+            .iter()
+            .map(|(number, name)| {
+                serde_json::from_value(serde_json::json!({
+                    "entry_number": number,
+                    "pokemon_species": { "name": name, "url": "" },
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_query_keeps_dex_order() {
+        let entries = entries(&[(1, "bulbasaur"), (2, "ivysaur"), (3, "venusaur")]);
+        assert_eq!(filter_entries(&entries, ""), [0, 1, 2]);
+        assert_eq!(filter_entries(&entries, " # "), [0, 1, 2]);
+    }
+
+    #[test]
+    fn exact_number_goes_before_prefixes() {
+        let entries = entries(&[(250, "ho-oh"), (52, "meowth"), (25, "pikachu"), (2, "ivysaur")]);
+        assert_eq!(filter_entries(&entries, "25"), [2, 0]);
+        assert_eq!(filter_entries(&entries, "#025"), [2, 0]);
+        // Prefixes tie and keep dex order
+        assert_eq!(filter_entries(&entries, "2"), [3, 0, 2]);
+    }
+
+    #[test]
+    fn numbers_beat_names_with_digits() {
+        // Regional dexes number species differently, porygon2 only matches by name here
+        let entries = entries(&[(150, "porygon2"), (151, "porygon-z"), (2, "ivysaur")]);
+        assert_eq!(filter_entries(&entries, "2"), [2, 0]);
+    }
+
+    #[test]
+    fn names_match_fuzzily() {
+        let entries = entries(&[(1, "bulbasaur"), (4, "charmander"), (5, "charmeleon"), (6, "charizard")]);
+        assert_eq!(filter_entries(&entries, "chzd"), [3]);
+        assert_eq!(filter_entries(&entries, "bsaur"), [0]);
+        let mut chars = filter_entries(&entries, "char");
+        chars.sort();
+        assert_eq!(chars, [1, 2, 3]);
+        assert!(filter_entries(&entries, "pikachu").is_empty());
+    }
+
+    #[test]
+    fn hidden_species_leave_the_cursor_on_the_next_row() {
+        let mut state = EntriesState {
+            entries: entries(&[(1, "bulbasaur"), (2, "ivysaur"), (3, "venusaur"), (4, "charmander"), (5, "charmeleon")]),
+            dex: "national".to_owned(),
+            ..Default::default()
+        };
+        state.apply_filter(None);
+        for caught in ["ivysaur", "charmander"] {
+            state.collection.toggle("national", caught, Mark::Caught).unwrap();
+        }
+        state.table_state.select(Some(1));
+        state.missing_only = true;
+        state.apply_filter_keeping_row();
+        assert_eq!(state.selected_name().as_deref(), Some("venusaur"));
+
+        // Catching the selected species moves on past the caught ones
+        state.collection.toggle("national", "venusaur", Mark::Caught).unwrap();
+        state.apply_filter_keeping_row();
+        assert_eq!(state.selected_name().as_deref(), Some("charmeleon"));
+
+        // And back up when it was the last one
+        state.collection.toggle("national", "charmeleon", Mark::Caught).unwrap();
+        state.apply_filter_keeping_row();
+        assert_eq!(state.selected_name().as_deref(), Some("bulbasaur"));
 
+        state.collection.toggle("national", "bulbasaur", Mark::Caught).unwrap();
+        state.apply_filter_keeping_row();
+        assert_eq!(state.selected_name(), None);
+    }
+
+    #[test]
+    fn failed_species_are_retried_when_asked() {
+        let mut state = EntriesState {
+            entries: entries(&[(1, "bulbasaur"), (2, "ivysaur")]),
+            ..Default::default()
+        };
+        assert!(state.missing_from_index(true).is_empty(), "nothing needs the index yet");
+        state.wide = true;
+        state.index_failed.insert("ivysaur".to_owned());
+        assert_eq!(state.missing_from_index(false), ["bulbasaur"]);
+        assert_eq!(state.missing_from_index(true), ["bulbasaur", "ivysaur"]);
+        assert!(state.index_failed.is_empty());
+    }
+}
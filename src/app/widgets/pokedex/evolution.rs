@@ -0,0 +1,155 @@
+use std::{fmt, sync::{Arc, RwLock}};
+
+use ratatui::widgets::ListState;
+use rustemon::model::{evolution::EvolutionChain, pokemon::PokemonSpecies, resource::ApiResource};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{events::{navigation::{NavDirection, Navigation}, AppEvent, Event}, fetch::{Priority, Scheduler}, pokemon::evolution::{flatten, EvolutionStep}};
+
+#[derive(Debug, Clone, Default)]
+pub enum LoadingState
+{
+    #[default]
+    Idle,
+    Loading(String),
+    Loaded(Vec<EvolutionStep>),
+    /// The species doesn't evolve and doesn't evolve from anything
+    NoChain,
+    Error(String)
+}
+
+impl fmt::Display for LoadingState
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            LoadingState::Idle => write!(f, "Unset"),
+            LoadingState::Loading(species) => write!(f, "Loading {0}", species),
+            LoadingState::Loaded(steps) => write!(f, "Loaded {0} stages", steps.len()),
+            LoadingState::NoChain => write!(f, "Does not evolve"),
+            LoadingState::Error(error) => write!(f, "Error {0}", error),
+        }
+    }
+}
+
+/// Evolution tree of the species shown in the details panel
+#[derive(Debug, Clone)]
+pub struct EvolutionWidget {
+    sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
+    pub state: Arc<RwLock<EvolutionState>>,
+}
+
+impl EvolutionWidget {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
+        Self {
+            sender,
+            scheduler,
+            state: Default::default(),
+        }
+    }
+
+    /// Moving within a chain that's already loaded only moves the selection
+    pub fn set_species(&self, species: &PokemonSpecies) {
+        let mut state = self.state.write().unwrap();
+        state.species = species.name.clone();
+        if let LoadingState::Loaded(steps) = &state.loading_state
+            && let Some(position) = steps.iter().position(|step| step.species == species.name)
+        {
+            state.list_state.select(Some(position));
+            let _ = self.sender.send(Event::App(AppEvent::Redraw));
+            return;
+        }
+        match &species.evolution_chain {
+            Some(chain) => {
+                state.loading_state = LoadingState::Loading(species.name.clone());
+                tokio::spawn(self.clone().fetch(species.name.clone(), chain.clone()));
+            }
+            None => state.loading_state = LoadingState::NoChain,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    /// Species of the selected stage
+    pub fn get_selected(&self) -> Option<String> {
+        let state = self.state.read().unwrap();
+        match &state.loading_state {
+            LoadingState::Loaded(steps) => state
+                .list_state
+                .selected()
+                .and_then(|i| steps.get(i))
+                .map(|step| step.species.clone()),
+            _ => None,
+        }
+    }
+
+    async fn fetch(self, species: String, chain: ApiResource<EvolutionChain>) {
+        let result = self.scheduler.follow(&chain, Priority::Visible).await;
+        let mut state = self.state.write().unwrap();
+        if state.species != species {
+            return;
+        }
+        state.loading_state = match result.map(|chain| flatten(&chain.chain)) {
+            Ok(steps) if steps.len() > 1 => {
+                let position = steps.iter().position(|step| step.species == species);
+                state.list_state.select(position);
+                LoadingState::Loaded(steps)
+            }
+            Ok(_) => LoadingState::NoChain,
+            Err(err) => LoadingState::Error(err.to_string()),
+        };
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EvolutionState {
+    focused: bool,
+    /// Species of the Pokémon in the details panel
+    species: String,
+    loading_state: LoadingState,
+    pub list_state: ListState,
+}
+
+impl EvolutionState {
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn species(&self) -> &str {
+        &self.species
+    }
+
+    pub fn loading_state(&self) -> &LoadingState {
+        &self.loading_state
+    }
+}
+
+impl Navigation for &EvolutionWidget {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        let count = match &state.loading_state {
+            LoadingState::Loaded(steps) => steps.len(),
+            _ => 0,
+        };
+        match direction {
+            NavDirection::Up => state.list_state.select_previous(),
+            NavDirection::Down if state.list_state.selected().is_some_and(|i| i + 1 < count) => {
+                state.list_state.select_next()
+            }
+            _ => return false,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
@@ -1,6 +1,9 @@
 pub mod entries;
+pub mod dex_picker;
 pub mod detail;
+pub mod evolution;
 pub mod abilities;
 pub mod ability;
 pub mod monmove;
-pub mod moves;
\ No newline at end of file
+pub mod moves;
+pub mod stats;
\ No newline at end of file
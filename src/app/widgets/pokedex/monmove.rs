@@ -1,11 +1,14 @@
 use std::{fmt, sync::{Arc, RwLock}};
 
 use ratatui::style::Style;
-use rustemon::{error::Error, model::{moves::Move, pokemon::PokemonMove}, Follow};
+use rustemon::model::{moves::Move, pokemon::PokemonMove};
 use tokio::{select, sync::mpsc::UnboundedSender};
 use tokio_util::sync::CancellationToken;
 
-use crate::{events::{AppEvent, Event}, pokemon::get_client};
+use crate::{
+    events::{AppEvent, Event},
+    fetch::{FetchError, Priority, Scheduler},
+};
 
 
 #[derive(Debug, Clone, Default)]
@@ -15,7 +18,7 @@ pub enum LoadingState
     Idle,
     Lazy(PokemonMove),
     Loading(PokemonMove),
-    Loaded(Move),
+    Loaded(Box<Move>),
     Error(String)
 }
 
@@ -37,6 +40,7 @@ impl fmt::Display for LoadingState
 pub struct MoveWidget {
     pub style: Style,
     sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
     pub state: Arc<RwLock<MoveState>>,
 }
 
@@ -45,7 +49,7 @@ impl MoveWidget {
         self.set_loading_state(LoadingState::Loading(move_.clone()));
 
         //self.set_loading_state(LoadingState::Loading);
-        match move_.move_.follow(&get_client()).await {
+        match self.scheduler.follow(&move_.move_, Priority::Visible).await {
             Ok(move_) => self.on_load(move_),
             Err(err) => self.on_err(err),
         }
@@ -54,12 +58,9 @@ impl MoveWidget {
     pub fn load(&self, cancellation_token: CancellationToken)
     {
         let state = self.state.read().unwrap();
-        match state.loading_state.clone(){
-            LoadingState::Lazy(pokemon_move) => {
-                let this = self.clone();
-                tokio::spawn(this.cancelable_fetch(pokemon_move, cancellation_token));
-            },
-            _ => {}
+        if let LoadingState::Lazy(pokemon_move) = state.loading_state.clone() {
+            let this = self.clone();
+            tokio::spawn(this.cancelable_fetch(pokemon_move, cancellation_token));
         }
     }
 
@@ -82,31 +83,25 @@ impl MoveWidget {
         self.state.write().unwrap().loading_state = state;
     }
 
-    fn on_err(&self, err: Error) {
+    fn on_err(&self, err: FetchError) {
         self.set_loading_state(LoadingState::Error(err.to_string()));
     }
 
     fn on_load(&self, move_: Move) {
         let mut state = self.state.write().unwrap();
-        match state.loading_state.clone()
+        if let LoadingState::Loading(pokmon_move) = &state.loading_state
+            && move_.name == pokmon_move.move_.name
         {
-            LoadingState::Loading(pokmon_move) => 
-            {
-                if move_.name == pokmon_move.move_.name
-                {
-                    state.loading_state = LoadingState::Loaded(move_);
-                    let _ = self.sender.send(Event::App(AppEvent::Redraw));
-
-                }
-            } ,
-            _ => {}
+            state.loading_state = LoadingState::Loaded(Box::new(move_));
+            let _ = self.sender.send(Event::App(AppEvent::Redraw));
         }
 
     }
 
-    pub fn new(sender: UnboundedSender<Event>, move_: PokemonMove) -> Self {
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler, move_: PokemonMove) -> Self {
         Self {
             sender: sender.clone(),
+            scheduler,
             style: Default::default(),
             state: Arc::new(RwLock::new(MoveState{loading_state: LoadingState::Lazy(move_)}))}
         }
@@ -126,6 +121,16 @@ impl MoveState {
         &self.loading_state
     }
 
+    pub fn name(&self) -> Option<&str> {
+        match &self.loading_state {
+            LoadingState::Idle | LoadingState::Error(_) => None,
+            LoadingState::Lazy(pokemon_move) | LoadingState::Loading(pokemon_move) => {
+                Some(&pokemon_move.move_.name)
+            }
+            LoadingState::Loaded(move_) => Some(&move_.name),
+        }
+    }
+
     
     
 }
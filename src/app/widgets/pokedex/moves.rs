@@ -1,30 +1,54 @@
-use std::{
-    cmp::min,
-    sync::{Arc, RwLock},
-};
+use std::sync::{Arc, RwLock};
 
-use rustemon::model::pokemon::{PokemonAbility, PokemonMove};
+use rustemon::model::pokemon::PokemonMove;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::sync::CancellationToken;
 use tui_widget_list::ListState;
 
-use crate::events::{
-    AppEvent, Event,
-    navigation::{NavDirection, Navigation},
+use crate::{
+    events::{
+        AppEvent, Event,
+        command::{Command, Commands},
+        navigation::{NavDirection, Navigation},
+        route::Route,
+    },
+    fetch::Scheduler,
+    pokemon::learnset::{LearnMethod, learnset, newest_version_group, version_groups},
 };
 
 use super::monmove::MoveWidget;
 
+/// A row of the list, the first move of each learn method also carries the group's header
+#[derive(Debug, Clone)]
+pub struct MoveEntry {
+    pub header: Option<LearnMethod>,
+    pub method: LearnMethod,
+    pub level: i64,
+    pub widget: MoveWidget,
+}
+
 #[derive(Debug, Clone)]
 pub struct MovesWidget {
     sender: UnboundedSender<Event>,
+    scheduler: Scheduler,
     pub state: Arc<RwLock<MovesState>>,
 }
 
 impl MovesWidget {
-    pub fn new(sender: UnboundedSender<Event>) -> Self {
+    /// Name of the move under the cursor
+    pub fn get_selected(&self) -> Option<String> {
+        let state = self.state.read().unwrap();
+        state
+            .list_state
+            .selected
+            .and_then(|i| state.widgets.get(i))
+            .and_then(|entry| entry.widget.state.read().unwrap().name().map(str::to_owned))
+    }
+
+    pub fn new(sender: UnboundedSender<Event>, scheduler: Scheduler) -> Self {
         Self {
             sender,
+            scheduler,
             state: Default::default(),
         }
     }
@@ -34,22 +58,67 @@ impl MovesWidget {
         let start = state.list_state.selected.unwrap_or(0);
         let end = usize::min(start + 15, state.widgets.len());
         for ele in state.widgets[start..end].iter() {
-            ele.load(state.cancelation_token.child_token());
+            ele.widget.load(state.cancelation_token.child_token());
         }
     }
 
+    /// Keeps the picked version group when the new Pokémon has moves in it, otherwise shows the
+    /// newest one
     pub fn set_moves(&self, moves: Vec<PokemonMove>) {
+        {
+            let mut state = self.state.write().unwrap();
+            state.version_groups = version_groups(&moves);
+            if !state
+                .version_group
+                .as_ref()
+                .is_some_and(|group| state.version_groups.contains(group))
+            {
+                state.version_group = newest_version_group(&state.version_groups).cloned();
+            }
+            state.moves = moves;
+        }
+        self.show_version_group();
+    }
+
+    /// Switches to the next version group the Pokémon learns moves in, wrapping back to the oldest
+    pub fn cycle_version_group(&self) {
+        {
+            let mut state = self.state.write().unwrap();
+            let next = state
+                .version_group
+                .as_ref()
+                .and_then(|group| state.version_groups.iter().position(|g| g == group))
+                .map_or(0, |i| (i + 1) % state.version_groups.len().max(1));
+            state.version_group = state.version_groups.get(next).cloned();
+        }
+        self.show_version_group();
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn show_version_group(&self) {
         {
             let mut state = self.state.write().unwrap();
             state.cancelation_token.cancel();
             state.cancelation_token = CancellationToken::new();
-            state.widgets.clear();
+            let learned = match &state.version_group {
+                Some(group) => learnset(&state.moves, group),
+                None => Vec::new(),
+            };
+            let mut previous = None;
+            state.widgets = learned
+                .into_iter()
+                .map(|learned| {
+                    let header = (previous.as_ref() != Some(&learned.method)).then(|| learned.method.clone());
+                    previous = Some(learned.method.clone());
+                    MoveEntry {
+                        header,
+                        method: learned.method,
+                        level: learned.level,
+                        widget: MoveWidget::new(self.sender.clone(), self.scheduler.clone(), learned.move_),
+                    }
+                })
+                .collect();
             state.list_state = ListState::default();
-            for move_ in moves {
-                state
-                    .widgets
-                    .push(MoveWidget::new(self.sender.clone(), move_));
-            }
             state.list_state.select(Some(0));
         }
         self.load();
@@ -60,7 +129,11 @@ impl MovesWidget {
 pub struct MovesState {
     focused: bool,
     cancelation_token : CancellationToken,
-    pub widgets: Vec<MoveWidget>,
+    /// Every move the Pokémon learns, across all games
+    moves: Vec<PokemonMove>,
+    version_groups: Vec<String>,
+    version_group: Option<String>,
+    pub widgets: Vec<MoveEntry>,
     pub list_state: ListState,
 }
 
@@ -68,6 +141,10 @@ impl MovesState {
     pub fn focused(&self) -> bool {
         self.focused
     }
+
+    pub fn version_group(&self) -> Option<&str> {
+        self.version_group.as_deref()
+    }
 }
 
 impl Navigation for &MovesWidget {
@@ -102,3 +179,18 @@ impl Navigation for &MovesWidget {
         let _ = self.sender.send(Event::App(AppEvent::Redraw));
     }
 }
+
+impl Commands for &MovesWidget {
+    fn handle_command(self, command: Command) -> bool {
+        let Command::Select = command else {
+            return false;
+        };
+        match self.get_selected() {
+            Some(name) => {
+                let _ = self.sender.send(Event::App(AppEvent::Push(Route::Move(name))));
+                true
+            }
+            None => false,
+        }
+    }
+}
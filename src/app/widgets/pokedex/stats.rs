@@ -0,0 +1,138 @@
+use std::sync::{Arc, RwLock};
+
+use rustemon::model::pokemon::PokemonStat;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    events::{command::{Command, Commands}, navigation::{NavDirection, Navigation}, AppEvent, Event},
+    pokemon::{stats::{base_stats, StatSpread, MAX_IV}, MonStat},
+};
+
+/// Level and nature come before the six stat rows
+pub const SPREAD_ROWS: usize = 2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StatsColumn {
+    #[default]
+    Iv,
+    Ev,
+}
+
+/// Base stats of the Pokémon in the details panel, or the real ones once the calculator is open
+#[derive(Debug, Clone)]
+pub struct StatsWidget {
+    sender: UnboundedSender<Event>,
+    pub state: Arc<RwLock<StatsState>>,
+}
+
+impl StatsWidget {
+    pub fn new(sender: UnboundedSender<Event>) -> Self {
+        Self {
+            sender,
+            state: Default::default(),
+        }
+    }
+
+    /// The spread is kept so the same build can be checked against several Pokémon
+    pub fn set_stats(&self, stats: &[PokemonStat]) {
+        self.state.write().unwrap().base = base_stats(stats);
+    }
+
+    /// Returns whether the calculator is now open
+    pub fn toggle_calculator(&self) -> bool {
+        let mut state = self.state.write().unwrap();
+        state.calculator = !state.calculator;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        state.calculator
+    }
+
+    fn adjust(&self, increase: bool) -> bool {
+        let mut state = self.state.write().unwrap();
+        if !state.calculator {
+            return false;
+        }
+        let (row, column) = (state.row, state.column);
+        let spread = &mut state.spread;
+        match (row, column) {
+            (0, _) => spread.step_level(increase),
+            (1, _) => spread.step_nature(increase),
+            (row, StatsColumn::Iv) => {
+                let iv = &mut spread.ivs[row - SPREAD_ROWS];
+                *iv = if increase { u32::min(*iv + 1, MAX_IV) } else { iv.saturating_sub(1) };
+            }
+            (row, StatsColumn::Ev) => spread.step_ev(MonStat::ALL[row - SPREAD_ROWS], increase),
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct StatsState {
+    focused: bool,
+    calculator: bool,
+    /// Indexed like [`MonStat::ALL`]
+    base: [u32; 6],
+    spread: StatSpread,
+    row: usize,
+    column: StatsColumn,
+}
+
+impl StatsState {
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn calculator(&self) -> bool {
+        self.calculator
+    }
+
+    pub fn base(&self) -> [u32; 6] {
+        self.base
+    }
+
+    pub fn spread(&self) -> &StatSpread {
+        &self.spread
+    }
+
+    /// Selected row and column, the column only matters on stat rows
+    pub fn selected(&self) -> (usize, StatsColumn) {
+        (self.row, self.column)
+    }
+}
+
+impl Navigation for &StatsWidget {
+    fn handle_navigation_input(self, direction: NavDirection) -> bool {
+        let mut state = self.state.write().unwrap();
+        let on_stat = state.row >= SPREAD_ROWS;
+        match (direction, state.column) {
+            (NavDirection::Up, _) if state.row > 0 => state.row -= 1,
+            (NavDirection::Down, _) if state.row + 1 < SPREAD_ROWS + MonStat::ALL.len() => state.row += 1,
+            (NavDirection::Tab, StatsColumn::Iv) if on_stat => state.column = StatsColumn::Ev,
+            (NavDirection::BackTab, StatsColumn::Ev) if on_stat => state.column = StatsColumn::Iv,
+            _ => return false,
+        }
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        true
+    }
+
+    fn focus(self) {
+        self.state.write().unwrap().focused = true;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn unfocus(self) {
+        self.state.write().unwrap().focused = false;
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+}
+
+impl Commands for &StatsWidget {
+    fn handle_command(self, command: Command) -> bool {
+        match command {
+            Command::Increase => self.adjust(true),
+            Command::Decrease => self.adjust(false),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+
+const FILE_NAME: &str = "collection.toml";
+
+/// Things a species can be marked as from the pokedex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Favorite,
+    Seen,
+    Caught,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Marks {
+    pub favorite: bool,
+    pub seen: bool,
+    pub caught: bool,
+}
+
+impl Marks {
+    fn get_mut(&mut self, mark: Mark) -> &mut bool {
+        match mark {
+            Mark::Favorite => &mut self.favorite,
+            Mark::Seen => &mut self.seen,
+            Mark::Caught => &mut self.caught,
+        }
+    }
+}
+
+/// Species marked in each dex, so a regional dex can be checked off separately from the national one
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    /// Dex name to species name, species with nothing marked are left out
+    #[serde(default)]
+    dexes: BTreeMap<String, BTreeMap<String, Marks>>,
+    /// Where it is saved, `None` keeps it in memory only
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Collection {
+    /// Reads the collection from `dir`, a missing file is an empty collection
+    pub fn load(dir: PathBuf) -> Result<Self> {
+        let path = dir.join(FILE_NAME);
+        let mut collection: Collection = match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents).map_err(|err| eyre!("invalid collection file {}: {err}", path.display()))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Collection::default(),
+            Err(err) => return Err(eyre!("couldn't read collection file {}: {err}", path.display())),
+        };
+        collection.path = Some(path);
+        Ok(collection)
+    }
+
+    pub fn marks(&self, dex: &str, species: &str) -> Marks {
+        self.dexes.get(dex).and_then(|marks| marks.get(species)).copied().unwrap_or_default()
+    }
+
+    /// Flips one mark and saves, catching a species also marks it as seen
+    pub fn toggle(&mut self, dex: &str, species: &str, mark: Mark) -> io::Result<Marks> {
+        let dex_marks = self.dexes.entry(dex.to_owned()).or_default();
+        let marks = dex_marks.entry(species.to_owned()).or_default();
+        let value = marks.get_mut(mark);
+        *value = !*value;
+        if mark == Mark::Caught && marks.caught {
+            marks.seen = true;
+        }
+        let marks = *marks;
+        if marks == Marks::default() {
+            dex_marks.remove(species);
+        }
+        if dex_marks.is_empty() {
+            self.dexes.remove(dex);
+        }
+        self.save()?;
+        Ok(marks)
+    }
+
+    /// Writes to a temporary file first so a crash can't leave half a collection behind
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = toml::to_string(self).map_err(io::Error::other)?;
+        let temporary = path.with_extension("toml.tmp");
+        fs::write(&temporary, contents)?;
+        fs::rename(temporary, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_and_saving() {
+        let dir = std::env::temp_dir().join(format!("rustydex-collection-{}", std::process::id()));
+        let mut collection = Collection::load(dir.clone()).unwrap();
+        assert_eq!(collection.marks("national", "pikachu"), Marks::default());
+
+        let marks = collection.toggle("national", "pikachu", Mark::Caught).unwrap();
+        assert_eq!(marks, Marks { favorite: false, seen: true, caught: true });
+        collection.toggle("national", "eevee", Mark::Favorite).unwrap();
+        collection.toggle("national", "eevee", Mark::Favorite).unwrap();
+        assert_eq!(collection.marks("kanto", "pikachu"), Marks::default());
+
+        let loaded = Collection::load(dir.clone()).unwrap();
+        assert_eq!(loaded, collection);
+        assert_eq!(loaded.dexes["national"].len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::Duration,
+};
+
+use clap::{Parser, ValueEnum};
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use serde::Deserialize;
+use url::Url;
+
+use crate::fetch::{DEFAULT_BACKOFF, DEFAULT_CONCURRENCY, DEFAULT_RATE_LIMIT, DEFAULT_RETRIES, DEFAULT_TIMEOUT};
+
+/// Cached responses older than this are dropped on startup
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Command line flags, every flag can also be set through its environment variable
+#[derive(Debug, Parser)]
+#[command(version, about = "A terminal Pokédex backed by PokeAPI")]
+struct Args {
+    /// Config file to read [default: $XDG_CONFIG_HOME/rustydex/config.toml]
+    #[arg(long, env = "RUSTYDEX_CONFIG")]
+    config: Option<PathBuf>,
+    /// Base URL of the PokeAPI to use, e.g. "http://127.0.0.1:8000/api/v2" [default: https://pokeapi.co/api/v2]
+    #[arg(long, env = "RUSTYDEX_ENDPOINT")]
+    endpoint: Option<String>,
+    /// Directory where PokeAPI responses are cached [default: $XDG_CACHE_HOME/rustydex]
+    #[arg(long, env = "RUSTYDEX_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+    /// How long cached responses are kept, e.g. "12h" or "30days"
+    #[arg(long, env = "RUSTYDEX_CACHE_TTL", value_parser = humantime::parse_duration)]
+    cache_ttl: Option<Duration>,
+    /// When to go to the network instead of the cache
+    #[arg(long, env = "RUSTYDEX_CACHE_MODE", value_enum)]
+    cache_mode: Option<CacheMode>,
+    /// Directory where favourites and caught Pokémon are saved [default: $XDG_DATA_HOME/rustydex]
+    #[arg(long, env = "RUSTYDEX_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+    /// How many requests PokeAPI is sent at once [default: 8]
+    #[arg(long, env = "RUSTYDEX_CONCURRENCY")]
+    concurrency: Option<usize>,
+    /// How many times a request that timed out or got a 5xx or 429 is sent again [default: 5]
+    #[arg(long, env = "RUSTYDEX_RETRIES")]
+    retries: Option<u32>,
+    /// Most requests per second sent to PokeAPI, 0 for no limit. Cache hits don't count [default: 20]
+    #[arg(long, env = "RUSTYDEX_RATE_LIMIT")]
+    rate_limit: Option<u32>,
+}
+
+/// Layout of the config file, everything is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    api: FileApiConfig,
+    cache: FileCacheConfig,
+    data: FileDataConfig,
+    fetch: FileFetchConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileApiConfig {
+    endpoint: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileCacheConfig {
+    dir: Option<PathBuf>,
+    ttl: Option<String>,
+    mode: Option<CacheMode>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileDataConfig {
+    dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileFetchConfig {
+    concurrency: Option<usize>,
+    retries: Option<u32>,
+    rate_limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheMode {
+    /// Use fresh cached responses and revalidate stale ones with PokeAPI
+    #[default]
+    Online,
+    /// Use any cached response, only going to PokeAPI on a miss
+    PreferCache,
+    /// Never go to PokeAPI, anything that isn't cached fails to load
+    OfflineOnly,
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub dir: PathBuf,
+    pub ttl: Duration,
+    pub mode: CacheMode,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            dir: dirs::cache_dir()
+                .map(|dir| dir.join("rustydex"))
+                .unwrap_or_else(|| PathBuf::from(".rustydex-cache")),
+            ttl: DEFAULT_CACHE_TTL,
+            mode: Default::default(),
+        }
+    }
+}
+
+/// Where the user's own data lives, unlike the cache it is never thrown away
+#[derive(Debug, Clone)]
+pub struct DataConfig {
+    pub dir: PathBuf,
+}
+
+impl Default for DataConfig {
+    fn default() -> Self {
+        Self {
+            dir: dirs::data_dir()
+                .map(|dir| dir.join("rustydex"))
+                .unwrap_or_else(|| PathBuf::from(".rustydex")),
+        }
+    }
+}
+
+/// How requests to PokeAPI are scheduled
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    pub concurrency: usize,
+    pub retries: u32,
+    /// Requests per second, 0 for no limit
+    pub rate_limit: u32,
+    /// Wait before the first retry
+    pub backoff: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            retries: DEFAULT_RETRIES,
+            rate_limit: DEFAULT_RATE_LIMIT,
+            backoff: DEFAULT_BACKOFF,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Custom PokeAPI deployment, `None` targets the public one
+    pub endpoint: Option<Url>,
+    pub cache: CacheConfig,
+    pub data: DataConfig,
+    pub fetch: FetchConfig,
+}
+
+impl Config {
+    /// Builds the config from, in order of precedence, CLI flags, environment variables, the
+    /// config file and the defaults
+    pub fn load() -> Result<Self> {
+        let args = Args::parse();
+        let file = read_config_file(args.config.as_ref())?;
+        let defaults = CacheConfig::default();
+        let file_ttl = file
+            .cache
+            .ttl
+            .map(|ttl| humantime::parse_duration(&ttl).wrap_err_with(|| format!("invalid cache.ttl `{ttl}`")))
+            .transpose()?;
+        let endpoint = args
+            .endpoint
+            .or(file.api.endpoint)
+            .map(|endpoint| parse_endpoint(&endpoint))
+            .transpose()?;
+        Ok(Self {
+            endpoint,
+            cache: CacheConfig {
+                dir: args.cache_dir.or(file.cache.dir).unwrap_or(defaults.dir),
+                ttl: args.cache_ttl.or(file_ttl).unwrap_or(defaults.ttl),
+                mode: args.cache_mode.or(file.cache.mode).unwrap_or(defaults.mode),
+            },
+            data: DataConfig {
+                dir: args.data_dir.or(file.data.dir).unwrap_or_else(|| DataConfig::default().dir),
+            },
+            fetch: FetchConfig {
+                concurrency: args.concurrency.or(file.fetch.concurrency).unwrap_or(DEFAULT_CONCURRENCY),
+                retries: args.retries.or(file.fetch.retries).unwrap_or(DEFAULT_RETRIES),
+                rate_limit: args.rate_limit.or(file.fetch.rate_limit).unwrap_or(DEFAULT_RATE_LIMIT),
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// Reads the config file, a missing file is only an error when its path was given explicitly
+fn read_config_file(explicit: Option<&PathBuf>) -> Result<FileConfig> {
+    let path = match explicit {
+        Some(path) => path.clone(),
+        None => match dirs::config_dir() {
+            Some(dir) => dir.join("rustydex").join("config.toml"),
+            None => return Ok(FileConfig::default()),
+        },
+    };
+    if explicit.is_none() && !path.exists() {
+        return Ok(FileConfig::default());
+    }
+    let contents = fs::read_to_string(&path)
+        .wrap_err_with(|| format!("couldn't read config file {}", path.display()))?;
+    toml::from_str(&contents).map_err(|err| eyre!("invalid config file {}: {err}", path.display()))
+}
+
+/// Checks the endpoint is an absolute http(s) URL, rustemon would accept anything `Url` parses
+fn parse_endpoint(endpoint: &str) -> Result<Url> {
+    let invalid = |reason: String| {
+        eyre!("invalid PokeAPI endpoint `{endpoint}`: {reason} (expected something like http://127.0.0.1:8000/api/v2)")
+    };
+    let mut url = Url::parse(endpoint).map_err(|err| invalid(err.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(format!("unsupported scheme `{}`", url.scheme())));
+    }
+    if url.host().is_none() {
+        return Err(invalid("missing host".to_owned()));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(invalid("query strings and fragments aren't supported".to_owned()));
+    }
+    // Resource paths are joined onto the endpoint, without the trailing slash the last
+    // segment would be replaced
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    Ok(url)
+}
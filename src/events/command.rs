@@ -0,0 +1,52 @@
+use crate::collection::Mark;
+
+/// Single key actions, dispatched down to the focused widget like [`super::navigation::NavDirection`]
+#[derive(Debug, Clone, Copy)]
+pub enum Command
+{
+    Select,
+    /// Close whatever is open, quits the app when nothing handles it
+    Cancel,
+    PickDex,
+    /// Switch the generation used for type matchups
+    CycleTypeChart,
+    /// Switch the game the moves list is shown for
+    CycleVersionGroup,
+    /// Switch between the forms of a species, like megas and regional variants
+    CycleForm,
+    /// Switch the stats between base stats and the stat calculator
+    ToggleStatCalculator,
+    /// Open the damage calculator, with the Pokémon and move on screen when there are some
+    DamageCalc,
+    /// Open the compare screen, pinning the Pokémon on screen when there is one
+    Compare,
+    /// Put the Pokémon, ability or move under the cursor on the team
+    AddToTeam,
+    /// Mark or unmark the selected species in the current dex
+    Mark(Mark),
+    /// Show only the species that haven't been caught yet
+    ToggleMissingOnly,
+    /// Order the entries by the next column: number, name, then each stat
+    CycleSort,
+    /// Flip the order the entries are sorted in
+    ReverseSort,
+    /// Show or hide the types and base stats next to the entries
+    ToggleColumns,
+    /// Start or stop filling the cache with every species in the dex
+    WarmCache,
+    /// Load a team from a Showdown paste file
+    Import,
+    /// Write the team out as a Showdown paste
+    Export,
+    /// Raise the selected value, like a level or an EV
+    Increase,
+    /// Lower the selected value
+    Decrease,
+    /// Take the selected thing off, like a team slot, one of its moves or a compared Pokémon
+    Remove,
+}
+
+pub trait Commands
+{
+    fn handle_command(self, command: Command) -> bool;
+}
@@ -0,0 +1,25 @@
+#[derive(Debug, Clone, Copy)]
+pub enum TextInput
+{
+    Char(char),
+    Backspace,
+    /// Keep the current text and stop editing
+    Submit,
+    /// Discard the current text and stop editing
+    Cancel,
+}
+
+/// Widgets that own a text box.
+///
+/// Text input is dispatched synchronously from the key handler rather than through the event
+/// channel, so keys typed right after opening the box can't race ahead of it.
+pub trait TextEditing
+{
+    /// Whether the widget is currently capturing raw key presses
+    fn editing(self) -> bool;
+
+    /// Open the text box, returns false if there is nothing to edit
+    fn start_editing(self) -> bool;
+
+    fn handle_text_input(self, input: TextInput) -> bool;
+}
@@ -1,13 +1,16 @@
+pub mod command;
+pub mod input;
 pub mod navigation;
+pub mod route;
 
 use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
+use command::Command;
 use navigation::NavDirection;
+use route::Route;
 use ratatui::crossterm::event::Event as CrosstermEvent;
 use tokio::sync::mpsc;
 
-/// The frequency at which tick events are emitted.
-
 /// Representation of all possible events.
 #[derive(Clone, Debug)]
 pub enum Event {
@@ -28,6 +31,25 @@ pub enum AppEvent {
     Redraw,
     Quit,
     Navigation(NavDirection),
+    Command(Command),
+    /// Open a screen on top of the current one
+    Push(Route),
+    /// Open a screen in place of the current one, without adding to the history
+    Replace(Route),
+    /// Go back to the previous screen
+    Back,
+    /// Undo the last [`AppEvent::Back`]
+    Forward,
+    /// Put something shown in the pokedex on the team
+    AddToTeam(TeamAddition),
+}
+
+/// What the pokedex can hand over to the team builder, by PokeAPI name
+#[derive(Clone, Debug)]
+pub enum TeamAddition {
+    Pokemon(String),
+    Ability(String),
+    Move(String),
 }
 
 /// Terminal event handler.
@@ -0,0 +1,19 @@
+/// Screens that can be opened from anywhere by sending [`super::AppEvent::Push`] or
+/// [`super::AppEvent::Replace`]
+#[derive(Debug, Clone)]
+pub enum Route
+{
+    TypeChart,
+    Natures,
+    Team,
+    /// Compare screen, pinning this Pokémon next to the ones already there
+    Compare(Option<String>),
+    /// Damage calculator, optionally filling in the attacker and the move
+    DamageCalc { attacker: Option<String>, move_: Option<String> },
+    /// Move detail screen for the move with this name
+    Move(String),
+    /// Ability detail screen for the ability with this name
+    Ability(String),
+    /// Details panel for the Pokémon with this name
+    Pokemon(String),
+}
@@ -0,0 +1,744 @@
+mod retry;
+
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    fmt,
+    future::Future,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures::{
+    FutureExt,
+    future::{BoxFuture, Shared, WeakShared},
+};
+use http::StatusCode;
+use http_cache_reqwest::{CACacheManager, Cache, CacheOptions, HttpCache, HttpCacheOptions};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+use rustemon::model::{
+    games::Pokedex,
+    locations::Region,
+    moves::Move,
+    pokemon::{Ability, Pokemon, PokemonSpecies},
+    resource::{ApiResource, NamedApiResource, NamedApiResourceList},
+};
+use serde::de::DeserializeOwned;
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use url::Url;
+
+use crate::{
+    config::{CacheMode, Config},
+    events::Event,
+};
+use retry::{RateLimit, Retry, Retrying};
+pub use retry::RetryStatus;
+
+/// Requests PokeAPI is sent at once when the config doesn't say
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Times a failed request is sent again when the config doesn't say
+pub const DEFAULT_RETRIES: u32 = 5;
+
+/// Requests per second PokeAPI is sent when the config doesn't say, PokeAPI asks for restraint
+/// rather than a number
+pub const DEFAULT_RATE_LIMIT: u32 = 20;
+
+/// Wait before the first retry, it doubles with each one after
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(250);
+
+/// A request that hasn't got its response by then is given up on, and retried
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const PUBLIC_ENDPOINT: &str = "https://pokeapi.co/api/v2/";
+
+/// Which waiting requests get the next free slot, earlier variants go first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Something on screen is blank until it arrives
+    Visible,
+    /// Needed for what is shown, like the filter index, but nothing is blank without it
+    Background,
+    /// Only filling the cache
+    Warm,
+}
+
+/// Errors are handed to every request that was waiting on the same fetch, so they are shared
+#[derive(Debug, Clone)]
+pub enum FetchError {
+    /// PokeAPI answered with something else than the resource, 404 for a name it doesn't know
+    Status(StatusCode),
+    /// Offline mode never goes to PokeAPI for what isn't cached
+    NotCached,
+    /// PokeAPI couldn't be reached, even after retrying
+    Request(Arc<reqwest_middleware::Error>),
+    /// The response isn't the resource that was asked for
+    Decode(Arc<reqwest::Error>),
+    /// A link in another resource that doesn't lead anywhere
+    InvalidUrl(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Status(StatusCode::NOT_FOUND) => write!(f, "not found"),
+            FetchError::Status(status) => write!(f, "PokeAPI answered {status}"),
+            FetchError::NotCached => write!(f, "not cached, and offline mode doesn't go to PokeAPI"),
+            FetchError::Request(err) => write!(f, "{err}"),
+            FetchError::Decode(err) => write!(f, "{err}"),
+            FetchError::InvalidUrl(url) => write!(f, "invalid link `{url}`"),
+        }
+    }
+}
+
+type SharedFetch<T> = Shared<BoxFuture<'static, Result<T, FetchError>>>;
+
+/// Every PokeAPI request goes through here so only so many run at once, what is on screen is
+/// fetched before anything in the background and the same resource is never requested twice at the
+/// same time. Failed requests are retried, giving their slot to other requests while they back
+/// off, and the ones that reach PokeAPI are rate limited
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    transport: Arc<Transport>,
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+/// How requests are sent, the same for the whole app
+#[derive(Debug)]
+struct Transport {
+    client: ClientWithMiddleware,
+    /// Resources are looked up by name under this
+    endpoint: Url,
+    offline: bool,
+    retrying: Retrying,
+    retry: Retry,
+}
+
+impl Scheduler {
+    /// Builds the client shared by the whole app and drops expired cache entries in the background.
+    /// Offline mode keeps everything since nothing could be fetched again.
+    pub fn new(sender: UnboundedSender<Event>, config: &Config) -> Result<Self, reqwest::Error> {
+        let Config { endpoint, cache, fetch, .. } = config;
+        let endpoint = endpoint.clone().unwrap_or_else(|| Url::parse(PUBLIC_ENDPOINT).unwrap());
+        if cache.mode != CacheMode::OfflineOnly {
+            let (dir, ttl) = (cache.dir.clone(), cache.ttl);
+            tokio::task::spawn_blocking(move || evict_expired(&dir, ttl));
+        }
+        let retrying = Retrying::new(sender, endpoint.clone(), fetch.retries);
+        let client = ClientBuilder::new(reqwest::Client::builder().timeout(fetch.timeout).build()?)
+            .with(Cache(HttpCache {
+                mode: match cache.mode {
+                    CacheMode::Online => http_cache_reqwest::CacheMode::Default,
+                    CacheMode::PreferCache => http_cache_reqwest::CacheMode::ForceCache,
+                    CacheMode::OfflineOnly => http_cache_reqwest::CacheMode::OnlyIfCached,
+                },
+                manager: CACacheManager {
+                    path: cache.dir.clone(),
+                },
+                options: HttpCacheOptions {
+                    cache_options: Some(CacheOptions {
+                        shared: false,
+                        cache_heuristic: 0.1,
+                        immutable_min_time_to_live: cache.ttl,
+                        ignore_cargo_cult: true,
+                    }),
+                    ..Default::default()
+                },
+            }))
+            .with(RateLimit::new(fetch.rate_limit))
+            .build();
+        Ok(Self {
+            transport: Arc::new(Transport {
+                client,
+                endpoint,
+                offline: cache.mode == CacheMode::OfflineOnly,
+                retry: Retry::new(retrying.clone(), fetch.backoff),
+                retrying,
+            }),
+            state: Arc::new(Mutex::new(SchedulerState {
+                limit: fetch.concurrency.max(1),
+                running: 0,
+                waiting: Default::default(),
+                in_flight: HashMap::new(),
+                next_id: 0,
+            })),
+        })
+    }
+
+    /// The request being retried, if any
+    pub fn retrying(&self) -> Option<RetryStatus> {
+        self.transport.retrying.status()
+    }
+
+    /// Runs `fetch` in a slot once one is free, unless a fetch with the same key is already queued
+    /// or running, then its result is shared. Dropping every request waiting on a fetch cancels it
+    async fn fetch<T, F, Fut>(&self, key: String, priority: Priority, fetch: F) -> Result<T, FetchError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce(Slot) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, FetchError>> + Send + 'static,
+    {
+        let shared = {
+            let mut state = self.state.lock().unwrap();
+            let running = state
+                .in_flight
+                .get(&key)
+                .and_then(|(_, fetch)| fetch.downcast_ref::<WeakShared<BoxFuture<'static, Result<T, FetchError>>>>())
+                .and_then(WeakShared::upgrade);
+            match running {
+                Some(shared) => shared,
+                None => {
+                    state.next_id += 1;
+                    let id = state.next_id;
+                    let guard = InFlightGuard {
+                        state: self.state.clone(),
+                        key: key.clone(),
+                        id,
+                    };
+                    let this = self.clone();
+                    let shared: SharedFetch<T> = async move {
+                        let _guard = guard;
+                        // Kept here as well so a fetch with no use for its slot can't give it up
+                        let permit = Arc::new(Mutex::new(Some(this.acquire(priority).await)));
+                        let slot = Slot {
+                            scheduler: this,
+                            priority,
+                            permit: permit.clone(),
+                        };
+                        fetch(slot).await
+                    }
+                    .boxed()
+                    .shared();
+                    if let Some(weak) = shared.downgrade() {
+                        state.in_flight.insert(key, (id, Box::new(weak)));
+                    }
+                    shared
+                }
+            }
+        };
+        shared.await
+    }
+
+    /// GETs `url` and reads the resource out of the response, requests for the same URL share one
+    async fn get<T>(&self, url: Url, priority: Priority) -> Result<T, FetchError>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let transport = self.transport.clone();
+        self.fetch(url.to_string(), priority, move |slot| async move {
+            let response = transport
+                .retry
+                .get(&transport.client, &url, &slot)
+                .await
+                .map_err(|err| FetchError::Request(Arc::new(err)))?;
+            match response.status() {
+                status if status.is_success() => {}
+                // What the cache answers when it can't go to the network for a miss
+                StatusCode::GATEWAY_TIMEOUT if transport.offline => return Err(FetchError::NotCached),
+                status => return Err(FetchError::Status(status)),
+            }
+            response.json().await.map_err(|err| FetchError::Decode(Arc::new(err)))
+        })
+        .await
+    }
+
+    /// `{endpoint}/{segments}/`, the trailing slash is how PokeAPI links to its resources
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.transport.endpoint.clone();
+        url.path_segments_mut()
+            .expect("the endpoint is an http(s) URL")
+            .pop_if_empty()
+            .extend(segments)
+            .push("");
+        url
+    }
+
+    /// Every resource of an endpoint, PokeAPI only lists the first 20 unless asked for more
+    async fn list<T>(&self, endpoint: &str, priority: Priority) -> Result<Vec<NamedApiResource<T>>, FetchError>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let url = self.url(&[endpoint]);
+        let first: NamedApiResourceList<T> = self.get(url.clone(), priority).await?;
+        let listed = first.results.len();
+        let count = usize::try_from(first.count).unwrap_or_default();
+        if listed >= count {
+            return Ok(first.results);
+        }
+        let mut rest_url = url;
+        rest_url.set_query(Some(&format!("offset={listed}&limit={}", count - listed)));
+        let rest: NamedApiResourceList<T> = self.get(rest_url, priority).await?;
+        Ok(first.results.into_iter().chain(rest.results).collect())
+    }
+
+    pub async fn pokemon(&self, name: &str, priority: Priority) -> Result<Pokemon, FetchError> {
+        self.get(self.url(&["pokemon", name]), priority).await
+    }
+
+    pub async fn species(&self, name: &str, priority: Priority) -> Result<PokemonSpecies, FetchError> {
+        self.get(self.url(&["pokemon-species", name]), priority).await
+    }
+
+    pub async fn move_(&self, name: &str, priority: Priority) -> Result<Move, FetchError> {
+        self.get(self.url(&["move", name]), priority).await
+    }
+
+    pub async fn ability(&self, name: &str, priority: Priority) -> Result<Ability, FetchError> {
+        self.get(self.url(&["ability", name]), priority).await
+    }
+
+    pub async fn pokedex(&self, name: &str, priority: Priority) -> Result<Pokedex, FetchError> {
+        self.get(self.url(&["pokedex", name]), priority).await
+    }
+
+    pub async fn regions(&self, priority: Priority) -> Result<Vec<NamedApiResource<Region>>, FetchError> {
+        self.list("region", priority).await
+    }
+
+    pub async fn pokedexes(&self, priority: Priority) -> Result<Vec<NamedApiResource<Pokedex>>, FetchError> {
+        self.list("pokedex", priority).await
+    }
+
+    pub async fn follow<T, R>(&self, resource: &R, priority: Priority) -> Result<T, FetchError>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+        R: Resource<T>,
+    {
+        let url = Url::parse(resource.url()).map_err(|_| FetchError::InvalidUrl(resource.url().to_owned()))?;
+        self.get(url, priority).await
+    }
+
+    /// Waits for a free slot, the slot is given back when the permit is dropped
+    async fn acquire(&self, priority: Priority) -> Permit {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            if state.running < state.limit {
+                state.running += 1;
+                return Permit(Some(self.state.clone()));
+            }
+            let (sender, receiver) = oneshot::channel();
+            state.waiting[priority as usize].push_back(sender);
+            receiver
+        };
+        // The state lives as long as any permit could be handed out, so this never fails
+        receiver.await.unwrap_or(Permit(None))
+    }
+}
+
+/// A link to another PokeAPI resource, named or not
+pub trait Resource<T> {
+    fn url(&self) -> &str;
+}
+
+impl<T> Resource<T> for NamedApiResource<T> {
+    fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl<T> Resource<T> for ApiResource<T> {
+    fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+fn evict_expired(dir: &Path, ttl: Duration) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let expired: Vec<String> = cacache::list_sync(dir)
+        .filter_map(Result::ok)
+        .filter(|entry| now.saturating_sub(entry.time) > ttl.as_millis())
+        .map(|entry| entry.key)
+        .collect();
+    for key in expired {
+        let _ = cacache::RemoveOpts::new()
+            .remove_fully(true)
+            .remove_sync(dir, key);
+    }
+}
+
+struct SchedulerState {
+    limit: usize,
+    running: usize,
+    /// A queue per priority of requests waiting for a slot
+    waiting: [VecDeque<oneshot::Sender<Permit>>; 3],
+    /// Queued and running fetches by key, each a `WeakShared` of the fetch's future so they stop
+    /// once nobody waits on them. The id tells a fetch apart from a later one with the same key
+    in_flight: HashMap<String, (u64, Box<dyn Any + Send>)>,
+    next_id: u64,
+}
+
+impl fmt::Debug for SchedulerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchedulerState")
+            .field("limit", &self.limit)
+            .field("running", &self.running)
+            .field("waiting", &self.waiting.iter().map(VecDeque::len).collect::<Vec<_>>())
+            .field("in_flight", &self.in_flight.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The slot a fetch runs in, given up while the fetch has nothing to send
+struct Slot {
+    scheduler: Scheduler,
+    /// What the fetch queued with, and queues with again for the slot back
+    priority: Priority,
+    permit: Arc<Mutex<Option<Permit>>>,
+}
+
+impl Slot {
+    /// Lets other requests run for `delay`, then waits for a free slot again
+    async fn give_up_for(&self, delay: Duration) {
+        self.permit.lock().unwrap().take();
+        tokio::time::sleep(delay).await;
+        let permit = self.scheduler.acquire(self.priority).await;
+        *self.permit.lock().unwrap() = Some(permit);
+    }
+}
+
+/// A slot to run a request in, passed on to the most urgent waiting request when dropped
+struct Permit(Option<Arc<Mutex<SchedulerState>>>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let Some(shared) = self.0.take() else {
+            return;
+        };
+        let mut state = shared.lock().unwrap();
+        while let Some(waiter) = state.waiting.iter_mut().find_map(VecDeque::pop_front) {
+            match waiter.send(Permit(Some(shared.clone()))) {
+                Ok(()) => return,
+                // That request was cancelled while it waited
+                Err(mut permit) => permit.0 = None,
+            }
+        }
+        state.running -= 1;
+    }
+}
+
+/// Forgets a fetch once it finishes or is cancelled, so the next request for its key starts anew
+struct InFlightGuard {
+    state: Arc<Mutex<SchedulerState>>,
+    key: String,
+    id: u64,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        if state.in_flight.get(&self.key).is_some_and(|(id, _)| *id == self.id) {
+            state.in_flight.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Instant,
+    };
+
+    use serde::Deserialize;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::{Notify, mpsc},
+        task::yield_now,
+        time::{sleep, timeout},
+    };
+
+    use super::*;
+    use crate::config::{CacheConfig, FetchConfig};
+
+    /// Without a stub `endpoint` the fetches never touch the client
+    fn scheduler(fetch: FetchConfig, endpoint: Option<Url>) -> Scheduler {
+        let config = Config {
+            endpoint,
+            cache: CacheConfig {
+                dir: std::env::temp_dir().join("rustydex-tests"),
+                ..Default::default()
+            },
+            fetch,
+            ..Default::default()
+        };
+        let (sender, _) = mpsc::unbounded_channel();
+        Scheduler::new(sender, &config).unwrap()
+    }
+
+    fn concurrency(concurrency: usize) -> Scheduler {
+        scheduler(FetchConfig { concurrency, ..Default::default() }, None)
+    }
+
+    /// Retries quickly so the tests don't spend their time backing off
+    fn retries(retries: u32) -> FetchConfig {
+        FetchConfig {
+            retries,
+            rate_limit: 0,
+            backoff: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    /// Holds the only slot until `release` is notified
+    fn occupy(scheduler: &Scheduler, release: Arc<Notify>) -> tokio::task::JoinHandle<Result<(), FetchError>> {
+        let scheduler = scheduler.clone();
+        tokio::spawn(async move {
+            scheduler
+                .fetch("busy".to_owned(), Priority::Visible, |_| async move {
+                    release.notified().await;
+                    Ok(())
+                })
+                .await
+        })
+    }
+
+    #[tokio::test]
+    async fn same_key_is_fetched_once() {
+        let scheduler = concurrency(4);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fetch = || {
+            let calls = calls.clone();
+            scheduler.fetch("pokemon/mew".to_owned(), Priority::Visible, |_| async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                yield_now().await;
+                Ok(151)
+            })
+        };
+        let (a, b) = futures::join!(fetch(), fetch());
+        assert_eq!((a.unwrap(), b.unwrap()), (151, 151));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        // Finished fetches are forgotten, the response cache is what keeps them
+        fetch().await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn visible_requests_go_first() {
+        let scheduler = concurrency(1);
+        let release = Arc::new(Notify::new());
+        let busy = occupy(&scheduler, release.clone());
+        yield_now().await;
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let queue = |key: &str, priority: Priority| {
+            let (scheduler, order, key) = (scheduler.clone(), order.clone(), key.to_owned());
+            tokio::spawn(async move {
+                scheduler
+                    .fetch(key.clone(), priority, |_| async move {
+                        order.lock().unwrap().push(key);
+                        Ok(())
+                    })
+                    .await
+            })
+        };
+        let warm = queue("warm", Priority::Warm);
+        let background = queue("background", Priority::Background);
+        let visible = queue("visible", Priority::Visible);
+        yield_now().await;
+        release.notify_one();
+        for task in [busy, warm, background, visible] {
+            task.await.unwrap().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), ["visible", "background", "warm"]);
+    }
+
+    #[tokio::test]
+    async fn cancelled_requests_give_their_slot_back() {
+        let scheduler = concurrency(1);
+        let release = Arc::new(Notify::new());
+        let busy = occupy(&scheduler, release.clone());
+        yield_now().await;
+        let never_run = scheduler.fetch("cancelled".to_owned(), Priority::Visible, |_| async { Ok(()) });
+        // Polled once so it is queued, then dropped
+        assert!(futures::poll!(Box::pin(never_run)).is_pending());
+        release.notify_one();
+        busy.await.unwrap().unwrap();
+        let next = scheduler.fetch("next".to_owned(), Priority::Warm, |_| async { Ok(1) });
+        let result = timeout(Duration::from_secs(1), next).await;
+        assert_eq!(result.expect("the slot was never given back").unwrap(), 1);
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Reply {
+        Status(u16),
+        Ok,
+        /// Never answers, the client times out
+        Stall,
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct Named {
+        name: String,
+    }
+
+    /// A PokeAPI stand-in giving each request the next reply, the last one is repeated. Answers are
+    /// never cached so every request reaches it
+    struct Stub {
+        endpoint: Url,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl Stub {
+        async fn start(replies: Vec<Reply>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let endpoint = Url::parse(&format!("http://{}/api/v2/", listener.local_addr().unwrap())).unwrap();
+            let requests = Arc::new(AtomicUsize::new(0));
+            let counter = requests.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let (counter, replies) = (counter.clone(), replies.clone());
+                    tokio::spawn(async move {
+                        let mut head = Vec::new();
+                        let mut buf = [0; 1024];
+                        while !head.ends_with(b"\r\n\r\n") {
+                            match stream.read(&mut buf).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(read) => head.extend_from_slice(&buf[..read]),
+                            }
+                        }
+                        let request = counter.fetch_add(1, Ordering::SeqCst);
+                        let path = String::from_utf8_lossy(&head).split(' ').nth(1).unwrap_or_default().to_owned();
+                        let (status, body) = match replies[request.min(replies.len() - 1)] {
+                            Reply::Status(status) => (status, String::new()),
+                            Reply::Ok => (200, format!(r#"{{"name":"{}"}}"#, path.trim_end_matches('/').rsplit('/').next().unwrap())),
+                            Reply::Stall => return sleep(Duration::from_secs(60)).await,
+                        };
+                        let response = format!(
+                            "HTTP/1.1 {status} Stub\r\nContent-Type: application/json\r\nCache-Control: no-store\r\n\
+                             Connection: close\r\nContent-Length: {}\r\n\r\n{body}",
+                            body.len()
+                        );
+                        let _ = stream.write_all(response.as_bytes()).await;
+                    });
+                }
+            });
+            Self { endpoint, requests }
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried() {
+        let stub = Stub::start(vec![Reply::Status(503), Reply::Status(429), Reply::Status(502), Reply::Ok]).await;
+        let scheduler = scheduler(retries(3), Some(stub.endpoint.clone()));
+        let mew: Named = scheduler.get(scheduler.url(&["pokemon", "mew"]), Priority::Visible).await.unwrap();
+        assert_eq!(mew.name, "mew");
+        assert_eq!(stub.requests(), 4);
+        assert_eq!(scheduler.retrying(), None);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_last_retry() {
+        let stub = Stub::start(vec![Reply::Status(500)]).await;
+        let scheduler = scheduler(retries(2), Some(stub.endpoint.clone()));
+        let result = scheduler.pokemon("mew", Priority::Visible).await;
+        assert!(matches!(result, Err(FetchError::Status(StatusCode::INTERNAL_SERVER_ERROR))));
+        assert_eq!(stub.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn missing_resources_are_not_retried() {
+        let stub = Stub::start(vec![Reply::Status(404), Reply::Ok]).await;
+        let scheduler = scheduler(retries(3), Some(stub.endpoint.clone()));
+        let result = scheduler.pokemon("missingno", Priority::Visible).await;
+        assert!(matches!(result, Err(FetchError::Status(StatusCode::NOT_FOUND))));
+        assert_eq!(stub.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn timeouts_are_retried() {
+        let stub = Stub::start(vec![Reply::Stall, Reply::Ok]).await;
+        let config = FetchConfig {
+            timeout: Duration::from_millis(200),
+            ..retries(3)
+        };
+        let scheduler = scheduler(config, Some(stub.endpoint.clone()));
+        let mew: Named = scheduler.get(scheduler.url(&["pokemon", "mew"]), Priority::Visible).await.unwrap();
+        assert_eq!(mew.name, "mew");
+        assert_eq!(stub.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_are_shown_while_they_wait() {
+        let stub = Stub::start(vec![Reply::Status(503), Reply::Ok]).await;
+        let config = FetchConfig {
+            backoff: Duration::from_millis(400),
+            ..retries(3)
+        };
+        let scheduler = scheduler(config, Some(stub.endpoint.clone()));
+        let url = scheduler.url(&["pokemon", "mew"]);
+        let fetch = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.get::<Named>(url, Priority::Visible).await }
+        });
+        let status = timeout(Duration::from_secs(1), async {
+            loop {
+                match scheduler.retrying() {
+                    Some(status) => return status,
+                    None => sleep(Duration::from_millis(5)).await,
+                }
+            }
+        })
+        .await
+        .expect("the retry was never shown");
+        let expected = RetryStatus {
+            path: "pokemon/mew".to_owned(),
+            attempt: 1,
+            retries: 3,
+            waiting: 1,
+        };
+        assert_eq!(status, expected);
+        fetch.await.unwrap().unwrap();
+        assert_eq!(scheduler.retrying(), None);
+    }
+
+    #[tokio::test]
+    async fn retries_give_their_slot_up_while_they_wait() {
+        let stub = Stub::start(vec![Reply::Status(503), Reply::Ok]).await;
+        let config = FetchConfig {
+            concurrency: 1,
+            backoff: Duration::from_secs(2),
+            ..retries(1)
+        };
+        let scheduler = scheduler(config, Some(stub.endpoint.clone()));
+        let url = scheduler.url(&["pokemon", "mew"]);
+        let mew = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.get::<Named>(url, Priority::Visible).await }
+        });
+        while scheduler.retrying().is_none() {
+            sleep(Duration::from_millis(5)).await;
+        }
+        // Waits at least a second before retrying, the only slot is free until then
+        let pikachu = scheduler.get::<Named>(scheduler.url(&["pokemon", "pikachu"]), Priority::Warm);
+        let pikachu = timeout(Duration::from_millis(500), pikachu).await;
+        assert_eq!(pikachu.expect("the retry held on to the slot").unwrap().name, "pikachu");
+        assert!(scheduler.retrying().is_some());
+        assert_eq!(mew.await.unwrap().unwrap().name, "mew");
+    }
+
+    #[tokio::test]
+    async fn requests_are_rate_limited() {
+        let stub = Stub::start(vec![Reply::Ok]).await;
+        let config = FetchConfig {
+            rate_limit: 20,
+            ..retries(0)
+        };
+        let scheduler = scheduler(config, Some(stub.endpoint.clone()));
+        let start = Instant::now();
+        let names = ["bulbasaur", "ivysaur", "venusaur", "charmander", "charmeleon"];
+        let fetches = names.map(|name| scheduler.get::<Named>(scheduler.url(&["pokemon", name]), Priority::Visible));
+        futures::future::try_join_all(fetches).await.unwrap();
+        // 50ms apart, the first goes straight away
+        assert!(start.elapsed() >= Duration::from_millis(200), "took {:?}", start.elapsed());
+        assert_eq!(stub.requests(), names.len());
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::BTreeMap,
+    hash::{BuildHasher, RandomState},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use http::{Extensions, StatusCode, header::RETRY_AFTER};
+use reqwest::{Request, Response};
+use reqwest_middleware::{ClientWithMiddleware, Error, Middleware, Next, Result};
+use tokio::{sync::mpsc::UnboundedSender, time::Instant};
+use url::Url;
+
+use super::Slot;
+use crate::events::{AppEvent, Event};
+
+/// Backoff between retries stops doubling here
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+/// Longest a `Retry-After` header is waited for, anything longer is treated as this
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// The request the title bar says is being retried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryStatus {
+    /// Relative to the endpoint, like `pokemon/pikachu`
+    pub path: String,
+    /// Retries made so far, the first request isn't one
+    pub attempt: u32,
+    pub retries: u32,
+    /// Every request waiting on a retry, this one included
+    pub waiting: usize,
+}
+
+/// Requests waiting to be retried, shared with the UI so a struggling PokeAPI doesn't look like a
+/// hung app
+#[derive(Debug, Clone)]
+pub struct Retrying {
+    sender: UnboundedSender<Event>,
+    endpoint: Url,
+    retries: u32,
+    /// Retries made so far by path
+    requests: Arc<Mutex<BTreeMap<String, u32>>>,
+}
+
+impl Retrying {
+    pub fn new(sender: UnboundedSender<Event>, endpoint: Url, retries: u32) -> Self {
+        Self {
+            sender,
+            endpoint,
+            retries,
+            requests: Default::default(),
+        }
+    }
+
+    pub fn status(&self) -> Option<RetryStatus> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().next().map(|(path, attempt)| RetryStatus {
+            path: path.clone(),
+            attempt: *attempt,
+            retries: self.retries,
+            waiting: requests.len(),
+        })
+    }
+
+    fn path(&self, url: &Url) -> String {
+        let path = self.endpoint.make_relative(url).unwrap_or_else(|| url.path().to_owned());
+        path.trim_end_matches('/').to_owned()
+    }
+
+    fn set(&self, path: &str, attempt: u32) {
+        self.requests.lock().unwrap().insert(path.to_owned(), attempt);
+        let _ = self.sender.send(Event::App(AppEvent::Redraw));
+    }
+
+    fn clear(&self, path: &str) {
+        if self.requests.lock().unwrap().remove(path).is_some() {
+            let _ = self.sender.send(Event::App(AppEvent::Redraw));
+        }
+    }
+}
+
+/// Takes a request off the retry status once it is done retrying, or cancelled while it waited
+struct RetryGuard<'a> {
+    retrying: &'a Retrying,
+    path: String,
+}
+
+impl Drop for RetryGuard<'_> {
+    fn drop(&mut self) {
+        self.retrying.clear(&self.path);
+    }
+}
+
+/// Sends a request again when it failed in a way that might not happen twice: timeouts, dropped
+/// connections, 5xx, 408 and 429. Each retry waits twice as long as the last, give or take some
+/// jitter so a batch of failed requests doesn't come back all at once. Other requests get the slot
+/// while a retry waits
+#[derive(Debug)]
+pub struct Retry {
+    retrying: Retrying,
+    /// Wait before the first retry
+    backoff: Duration,
+}
+
+impl Retry {
+    pub fn new(retrying: Retrying, backoff: Duration) -> Self {
+        Self { retrying, backoff }
+    }
+
+    /// GETs `url` until PokeAPI answers with something retrying won't change
+    pub(super) async fn get(&self, client: &ClientWithMiddleware, url: &Url, slot: &Slot) -> Result<Response> {
+        let guard = RetryGuard {
+            retrying: &self.retrying,
+            path: self.retrying.path(url),
+        };
+        let mut attempt = 0;
+        loop {
+            let result = client.get(url.clone()).send().await;
+            if attempt == self.retrying.retries || !is_transient(&result) {
+                return result;
+            }
+            attempt += 1;
+            let delay = backoff(self.backoff, attempt).max(retry_after(&result));
+            guard.retrying.set(&guard.path, attempt);
+            slot.give_up_for(delay).await;
+        }
+    }
+}
+
+fn is_transient(result: &Result<Response>) -> bool {
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            status.is_server_error() || matches!(status, StatusCode::TOO_MANY_REQUESTS | StatusCode::REQUEST_TIMEOUT)
+        }
+        Err(err) => is_transient_error(err),
+    }
+}
+
+fn is_transient_error(err: &Error) -> bool {
+    match err {
+        Error::Reqwest(err) => err.is_timeout() || err.is_connect() || err.is_request(),
+        // The cache passes on what the client failed with wrapped up
+        Error::Middleware(err) => err
+            .downcast_ref::<Box<dyn std::error::Error + Send + Sync>>()
+            .and_then(|err| err.downcast_ref::<Error>())
+            .is_some_and(is_transient_error),
+    }
+}
+
+/// How long PokeAPI asked to be left alone for, only the seconds form of `Retry-After` is read
+fn retry_after(result: &Result<Response>) -> Duration {
+    let Ok(response) = result else {
+        return Duration::ZERO;
+    };
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map_or(Duration::ZERO, |seconds| Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// Half of the doubled wait is always waited, the other half is random
+fn backoff(base: Duration, attempt: u32) -> Duration {
+    let doubled = base.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1))).min(MAX_BACKOFF);
+    doubled / 2 + jitter(doubled / 2)
+}
+
+/// Anywhere from nothing to `max`, a freshly keyed hasher is random enough to spread retries out
+fn jitter(max: Duration) -> Duration {
+    let random = RandomState::new().hash_one(());
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+/// Spaces out the requests that reach PokeAPI, cache hits never get this far
+#[derive(Debug)]
+pub struct RateLimit {
+    interval: Duration,
+    /// When the next request may start
+    next: Mutex<Instant>,
+}
+
+impl RateLimit {
+    /// No limit when `per_second` is 0
+    pub fn new(per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1).checked_div(per_second).unwrap_or_default(),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimit {
+    async fn handle(&self, req: Request, extensions: &mut Extensions, next: Next<'_>) -> Result<Response> {
+        let start = {
+            let mut slot = self.next.lock().unwrap();
+            let start = Instant::now().max(*slot);
+            *slot = start + self.interval;
+            start
+        };
+        tokio::time::sleep_until(start).await;
+        next.run(req, extensions).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let base = Duration::from_millis(100);
+        for (attempt, doubled) in [(1, 100), (2, 200), (3, 400), (4, 800)] {
+            let doubled = Duration::from_millis(doubled);
+            let delay = backoff(base, attempt);
+            assert!(delay >= doubled / 2 && delay <= doubled, "retry {attempt} waited {delay:?}");
+        }
+        assert!(backoff(base, 30) <= MAX_BACKOFF);
+        assert!(backoff(base, 30) >= MAX_BACKOFF / 2);
+    }
+}
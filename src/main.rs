@@ -1,15 +1,29 @@
 mod app;
+mod collection;
+mod config;
 mod events;
+mod fetch;
 mod pokemon;
 use app::App;
 use color_eyre::Result;
+use config::Config;
+use crossterm::{
+    event::{DisableBracketedPaste, EnableBracketedPaste},
+    execute,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     console_subscriber::init();
     color_eyre::install()?;
+    let config = Config::load()?;
+    let app = App::new(&config)?;
     let terminal = ratatui::init();
-    let app_result = App::default().run(terminal).await;
+    // Pastes arrive as one event instead of a stream of key presses, so teams can be pasted in.
+    // Terminals without it still work, and the terminal has to be restored either way
+    let _ = execute!(std::io::stdout(), EnableBracketedPaste);
+    let app_result = app.run(terminal).await;
+    let _ = execute!(std::io::stdout(), DisableBracketedPaste);
     ratatui::restore();
     app_result
 }
\ No newline at end of file
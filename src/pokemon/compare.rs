@@ -0,0 +1,56 @@
+use std::collections::BTreeSet;
+
+/// Names every list has, and the names only one of the lists has. Names some but not all of the
+/// lists have are in neither, and a single list shares everything. Everything comes back sorted so
+/// columns line up
+pub fn shared_and_unique(lists: &[Vec<String>]) -> (Vec<String>, Vec<Vec<String>>) {
+    let sets: Vec<BTreeSet<&String>> = lists.iter().map(|list| list.iter().collect()).collect();
+    let Some((first, rest)) = sets.split_first() else {
+        return (Vec::new(), Vec::new());
+    };
+    let shared: BTreeSet<&String> = first.iter().copied().filter(|name| rest.iter().all(|set| set.contains(name))).collect();
+    let unique = sets
+        .iter()
+        .enumerate()
+        .map(|(i, set)| {
+            set.difference(&shared)
+                .filter(|name| sets.iter().enumerate().all(|(j, other)| j == i || !other.contains(*name)))
+                .map(|name| (*name).clone())
+                .collect()
+        })
+        .collect();
+    (shared.into_iter().cloned().collect(), unique)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn splits_shared_from_unique() {
+        let lists = [names(&["tackle", "ember", "growl"]), names(&["growl", "tackle", "bubble"]), names(&["tackle", "growl", "growl"])];
+        let (shared, unique) = shared_and_unique(&lists);
+        assert_eq!(shared, names(&["growl", "tackle"]));
+        assert_eq!(unique, [names(&["ember"]), names(&["bubble"]), names(&[])]);
+    }
+
+    #[test]
+    fn names_in_some_lists_are_neither() {
+        let lists = [names(&["surf", "ice-beam", "tackle"]), names(&["ice-beam", "tackle", "ember"]), names(&["tackle", "growl"])];
+        let (shared, unique) = shared_and_unique(&lists);
+        assert_eq!(shared, names(&["tackle"]));
+        assert_eq!(unique, [names(&["surf"]), names(&["ember"]), names(&["growl"])]);
+    }
+
+    #[test]
+    fn one_list_shares_everything() {
+        let (shared, unique) = shared_and_unique(&[names(&["b", "a"])]);
+        assert_eq!(shared, names(&["a", "b"]));
+        assert_eq!(unique, [names(&[])]);
+        assert_eq!(shared_and_unique(&[]), (Vec::new(), Vec::new()));
+    }
+}
@@ -0,0 +1,320 @@
+use std::fmt;
+
+use rustemon::model::moves::Move;
+
+use super::{effectiveness::TypeChart, MonStat, MonType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Physical,
+    Special,
+}
+
+impl Category {
+    /// Stat used by the attacker and stat used by the defender
+    pub fn stats(self) -> (MonStat, MonStat) {
+        match self {
+            Category::Physical => (MonStat::Attack, MonStat::Defense),
+            Category::Special => (MonStat::SpecialAttack, MonStat::SpecialDefense),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Sun,
+    Rain,
+    Sandstorm,
+    /// Gen 9's snow, which replaced hail
+    Snow,
+}
+
+impl Weather {
+    pub const ALL: [Weather; 5] = [Weather::Clear, Weather::Sun, Weather::Rain, Weather::Sandstorm, Weather::Snow];
+
+    /// The weather after this one, wrapping back to the first
+    pub fn next(self) -> Self {
+        Weather::ALL[(self as usize + 1) % Weather::ALL.len()]
+    }
+
+    /// The weather before this one, wrapping back to the last
+    pub fn previous(self) -> Self {
+        Weather::ALL[(self as usize + Weather::ALL.len() - 1) % Weather::ALL.len()]
+    }
+}
+
+impl fmt::Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The parts of a move that matter for damage
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageMove {
+    pub power: u32,
+    pub type_: MonType,
+    pub category: Category,
+}
+
+impl DamageMove {
+    /// None for status moves and moves without a fixed power
+    pub fn from_move(move_: &Move) -> Option<Self> {
+        let category = match move_.damage_class.name.as_str() {
+            "physical" => Category::Physical,
+            "special" => Category::Special,
+            _ => return None,
+        };
+        Some(Self {
+            power: move_.power.filter(|power| *power > 0)? as u32,
+            type_: move_.type_.name.parse().ok()?,
+            category,
+        })
+    }
+}
+
+/// One side of the calculation, stats are the real ones rather than base stats
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combatant {
+    pub level: u32,
+    pub types: Vec<MonType>,
+    /// Indexed like [`MonStat::ALL`]
+    pub stats: [u32; 6],
+}
+
+impl Combatant {
+    pub fn stat(&self, stat: MonStat) -> u32 {
+        let index = MonStat::ALL.iter().position(|s| *s == stat).unwrap_or(0);
+        self.stats[index]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Conditions {
+    pub critical: bool,
+    pub weather: Weather,
+    /// Whether the attacker is burned, which halves physical damage
+    pub burned: bool,
+    /// Picks the matchups, and the generation for the modifiers that changed since Gen 5
+    pub chart: TypeChart,
+}
+
+/// Every damage roll of a move against a defender
+#[derive(Debug, Clone, PartialEq)]
+pub struct Damage {
+    /// From the lowest random factor (85%) to the highest (100%)
+    pub rolls: [u32; 16],
+    /// The defender's max HP
+    pub hp: u32,
+}
+
+impl Damage {
+    pub fn min(&self) -> u32 {
+        self.rolls[0]
+    }
+
+    pub fn max(&self) -> u32 {
+        self.rolls[15]
+    }
+
+    /// Lowest and highest roll as a percentage of the defender's HP
+    pub fn percent(&self) -> (f64, f64) {
+        let percent = |damage: u32| damage as f64 * 100.0 / self.hp.max(1) as f64;
+        (percent(self.min()), percent(self.max()))
+    }
+
+    /// Fewest hits that can KO from full HP, up to four, and the chance they do
+    pub fn ko_chance(&self) -> KoChance {
+        const MAX_HITS: u32 = 4;
+        if self.max() == 0 {
+            return KoChance::Never;
+        }
+        // How many roll combinations add up to each total, one more hit at a time
+        let mut totals = vec![1u64];
+        for hits in 1..=MAX_HITS {
+            let mut next = vec![0u64; totals.len() + self.max() as usize];
+            for (total, count) in totals.iter().enumerate().filter(|(_, count)| **count > 0) {
+                for roll in self.rolls {
+                    next[total + roll as usize] += count;
+                }
+            }
+            totals = next;
+            let kos: u64 = totals.iter().skip(self.hp as usize).sum();
+            if kos > 0 {
+                let chance = kos as f64 / 16u64.pow(hits) as f64;
+                return if kos == 16u64.pow(hits) {
+                    KoChance::Guaranteed(hits)
+                } else {
+                    KoChance::Chance(hits, chance)
+                };
+            }
+        }
+        KoChance::Never
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KoChance {
+    Guaranteed(u32),
+    /// Number of hits and the chance, between 0 and 1
+    Chance(u32, f64),
+    /// No damage, or more than four hits needed
+    Never,
+}
+
+impl fmt::Display for KoChance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hits = |hits: u32| if hits == 1 { "OHKO".to_owned() } else { format!("{hits}HKO") };
+        match self {
+            KoChance::Guaranteed(n) => write!(f, "guaranteed {}", hits(*n)),
+            KoChance::Chance(n, chance) => write!(f, "{:.1}% chance to {}", chance * 100.0, hits(*n)),
+            KoChance::Never => write!(f, "no KO in 4 hits"),
+        }
+    }
+}
+
+/// Multiplies by `multiplier / 4096` rounding halves down, the way the games apply modifiers
+fn modify(value: u32, multiplier: u32) -> u32 {
+    ((value as u64 * multiplier as u64 + 2047) / 4096) as u32
+}
+
+/// Gen 5+ damage formula, modifiers are applied in the same order as the games. Critical hits are
+/// 1.5x on the Gen 6+ chart and 2x before it, and snow only raises Defense on the Gen 6+ chart
+/// since it is Gen 9's snow
+pub fn calculate(attacker: &Combatant, defender: &Combatant, move_: &DamageMove, conditions: &Conditions) -> Damage {
+    let hp = defender.stat(MonStat::HP);
+    let effectiveness = move_.type_.multiplier_against_types(&defender.types, conditions.chart);
+    if effectiveness == 0.0 {
+        return Damage { rolls: [0; 16], hp };
+    }
+    let (attack_stat, defense_stat) = move_.category.stats();
+    let attack = attacker.stat(attack_stat) as u64;
+    let mut defense = defender.stat(defense_stat) as u64;
+    // Rock types get more special defense in a sandstorm, ice types more defense in snow
+    match (conditions.weather, move_.category) {
+        (Weather::Sandstorm, Category::Special) if defender.types.contains(&MonType::Rock) => defense = defense * 3 / 2,
+        (Weather::Snow, Category::Physical)
+            if conditions.chart == TypeChart::Gen6On && defender.types.contains(&MonType::Ice) =>
+        {
+            defense = defense * 3 / 2
+        }
+        _ => {}
+    }
+
+    let level_factor = 2 * attacker.level as u64 / 5 + 2;
+    let mut base = (level_factor * move_.power as u64 * attack / defense.max(1) / 50 + 2) as u32;
+    base = match (conditions.weather, move_.type_) {
+        (Weather::Sun, MonType::Fire) | (Weather::Rain, MonType::Water) => modify(base, 6144),
+        (Weather::Sun, MonType::Water) | (Weather::Rain, MonType::Fire) => modify(base, 2048),
+        _ => base,
+    };
+    if conditions.critical {
+        base = match conditions.chart {
+            TypeChart::Gen6On => modify(base, 6144),
+            TypeChart::Gen1 | TypeChart::Gen2To5 => base * 2,
+        };
+    }
+
+    let stab = attacker.types.contains(&move_.type_);
+    let burned = conditions.burned && move_.category == Category::Physical;
+    let rolls = std::array::from_fn(|roll| {
+        let mut damage = base * (85 + roll as u32) / 100;
+        if stab {
+            damage = modify(damage, 6144);
+        }
+        // Multipliers are powers of two so this floors like repeated halving would
+        damage = (damage as f64 * effectiveness as f64).floor() as u32;
+        if burned {
+            damage = modify(damage, 2048);
+        }
+        damage.max(1)
+    });
+    Damage { rolls, hp }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combatant(level: u32, types: &[MonType], stats: [u32; 6]) -> Combatant {
+        Combatant { level, types: types.to_vec(), stats }
+    }
+
+    /// Level 75 Glaceon using Ice Fang on a level 65 Garchomp, from the Bulbapedia damage article
+    fn ice_fang(conditions: Conditions, hp: u32) -> Damage {
+        let glaceon = combatant(75, &[MonType::Ice], [0, 123, 0, 0, 0, 0]);
+        let garchomp = combatant(65, &[MonType::Dragon, MonType::Ground], [hp, 0, 163, 0, 0, 0]);
+        let ice_fang = DamageMove { power: 65, type_: MonType::Ice, category: Category::Physical };
+        calculate(&glaceon, &garchomp, &ice_fang, &conditions)
+    }
+
+    #[test]
+    fn matches_the_worked_example() {
+        let damage = ice_fang(Conditions::default(), 200);
+        assert_eq!(
+            damage.rolls,
+            [168, 168, 168, 172, 172, 172, 180, 180, 180, 184, 184, 184, 192, 192, 192, 196]
+        );
+        assert_eq!(damage.percent(), (84.0, 98.0));
+    }
+
+    #[test]
+    fn burn_halves_physical_damage() {
+        let damage = ice_fang(Conditions { burned: true, ..Default::default() }, 200);
+        assert_eq!((damage.min(), damage.max()), (84, 98));
+    }
+
+    #[test]
+    fn rain_boosted_critical_hit() {
+        let attacker = combatant(50, &[MonType::Water], [0, 0, 0, 150, 0, 0]);
+        let defender = combatant(50, &[MonType::Normal], [150, 0, 0, 0, 100, 0]);
+        let surf = DamageMove { power: 90, type_: MonType::Water, category: Category::Special };
+        let conditions = Conditions { critical: true, weather: Weather::Rain, ..Default::default() };
+        let damage = calculate(&attacker, &defender, &surf, &conditions);
+        assert_eq!((damage.min(), damage.max()), (172, 204));
+        assert_eq!(damage.ko_chance(), KoChance::Guaranteed(1));
+    }
+
+    #[test]
+    fn critical_hits_are_doubled_before_gen_6() {
+        let critical = |chart| ice_fang(Conditions { critical: true, chart, ..Default::default() }, 200);
+        assert_eq!(critical(TypeChart::Gen6On).max(), 292);
+        assert_eq!(critical(TypeChart::Gen2To5).max(), 396);
+    }
+
+    #[test]
+    fn snow_only_raises_defense_on_the_gen_6_chart() {
+        let attacker = combatant(50, &[MonType::Fire], [0, 150, 0, 0, 0, 0]);
+        let defender = combatant(50, &[MonType::Ice], [150, 0, 100, 0, 0, 0]);
+        let slash = DamageMove { power: 70, type_: MonType::Normal, category: Category::Physical };
+        let damage = |weather, chart| calculate(&attacker, &defender, &slash, &Conditions { weather, chart, ..Default::default() }).max();
+        assert!(damage(Weather::Snow, TypeChart::Gen6On) < damage(Weather::Clear, TypeChart::Gen6On));
+        assert_eq!(damage(Weather::Snow, TypeChart::Gen2To5), damage(Weather::Clear, TypeChart::Gen2To5));
+    }
+
+    #[test]
+    fn ko_chances() {
+        assert_eq!(ice_fang(Conditions::default(), 180).ko_chance(), KoChance::Chance(1, 0.625));
+        assert_eq!(ice_fang(Conditions::default(), 200).ko_chance(), KoChance::Guaranteed(2));
+        assert_eq!(ice_fang(Conditions::default(), 1000).ko_chance(), KoChance::Never);
+        assert_eq!(KoChance::Chance(1, 0.625).to_string(), "62.5% chance to OHKO");
+        assert_eq!(KoChance::Guaranteed(2).to_string(), "guaranteed 2HKO");
+    }
+
+    #[test]
+    fn immunities_and_minimum_damage() {
+        let normal = DamageMove { power: 40, type_: MonType::Normal, category: Category::Physical };
+        let attacker = combatant(100, &[MonType::Normal], [0, 300, 0, 0, 0, 0]);
+        let ghost = combatant(100, &[MonType::Ghost], [100, 0, 100, 0, 0, 0]);
+        let damage = calculate(&attacker, &ghost, &normal, &Conditions::default());
+        assert_eq!(damage.rolls, [0; 16]);
+        assert_eq!(damage.ko_chance(), KoChance::Never);
+
+        let weak = combatant(1, &[MonType::Fire], [0, 1, 0, 0, 0, 0]);
+        let wall = combatant(100, &[MonType::Rock, MonType::Water], [100, 0, 999, 0, 0, 0]);
+        let ember = DamageMove { power: 10, type_: MonType::Fire, category: Category::Physical };
+        assert_eq!(calculate(&weak, &wall, &ember, &Conditions::default()).min(), 1);
+    }
+}
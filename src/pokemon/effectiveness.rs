@@ -0,0 +1,490 @@
+use std::str::FromStr;
+
+use rustemon::model::pokemon::{PokemonType, PokemonTypePast};
+
+use super::{filter::generation_number, MonType};
+
+/// The type chart was only reworked twice, every generation uses one of these
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypeChart {
+    /// Red, Blue and Yellow, before Dark and Steel existed
+    Gen1,
+    /// Gold and Silver up to Black and White 2, before Fairy existed
+    Gen2To5,
+    #[default]
+    Gen6On,
+}
+
+impl TypeChart {
+    pub const ALL: [TypeChart; 3] = [TypeChart::Gen1, TypeChart::Gen2To5, TypeChart::Gen6On];
+
+    /// The chart after this one, wrapping back to the first
+    pub fn next(self) -> Self {
+        let index = TypeChart::ALL.iter().position(|chart| *chart == self).unwrap_or(0);
+        TypeChart::ALL[(index + 1) % TypeChart::ALL.len()]
+    }
+
+    /// The chart before this one, wrapping back to the last
+    pub fn previous(self) -> Self {
+        let index = TypeChart::ALL.iter().position(|chart| *chart == self).unwrap_or(0);
+        TypeChart::ALL[(index + TypeChart::ALL.len() - 1) % TypeChart::ALL.len()]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TypeChart::Gen1 => "Gen 1",
+            TypeChart::Gen2To5 => "Gen 2-5",
+            TypeChart::Gen6On => "Gen 6+",
+        }
+    }
+
+    /// Types that exist on this chart, in the usual order
+    pub fn types(self) -> impl Iterator<Item = MonType> {
+        MonType::ALL.into_iter().filter(move |t| t.exists_in(self))
+    }
+
+    /// The last generation that used this chart, `None` for the current one
+    fn last_generation(self) -> Option<u8> {
+        match self {
+            TypeChart::Gen1 => Some(1),
+            TypeChart::Gen2To5 => Some(5),
+            TypeChart::Gen6On => None,
+        }
+    }
+}
+
+/// The types a Pokémon had while `chart` was in use, like Clefairy being Normal before Fairy
+/// existed. Each past entry holds the types up to and including its generation
+pub fn types_under(types: &[PokemonType], past_types: &[PokemonTypePast], chart: TypeChart) -> Vec<MonType> {
+    let past = chart.last_generation().and_then(|last| {
+        past_types
+            .iter()
+            .filter_map(|past| Some((generation_number(&past.generation.name)?, &past.types)))
+            .filter(|(generation, _)| *generation >= last)
+            .min_by_key(|(generation, _)| *generation)
+            .map(|(_, types)| types.as_slice())
+    });
+    past.unwrap_or(types)
+        .iter()
+        .filter_map(|t| MonType::from_str(&t.type_.name).ok())
+        .collect()
+}
+
+impl MonType {
+    pub const ALL: [MonType; 18] = [
+        MonType::Normal,
+        MonType::Fire,
+        MonType::Water,
+        MonType::Electric,
+        MonType::Grass,
+        MonType::Ice,
+        MonType::Fighting,
+        MonType::Poison,
+        MonType::Ground,
+        MonType::Flying,
+        MonType::Psychic,
+        MonType::Bug,
+        MonType::Rock,
+        MonType::Ghost,
+        MonType::Dragon,
+        MonType::Dark,
+        MonType::Steel,
+        MonType::Fairy,
+    ];
+
+    pub fn exists_in(&self, chart: TypeChart) -> bool {
+        match self {
+            MonType::Dark | MonType::Steel => chart != TypeChart::Gen1,
+            MonType::Fairy => chart == TypeChart::Gen6On,
+            _ => true,
+        }
+    }
+
+    /// Damage multiplier of a move of this type against a single `defending` type.
+    /// Types missing from `chart` never change the damage.
+    pub fn multiplier_against(self, defending: MonType, chart: TypeChart) -> f32 {
+        use MonType::*;
+        if !self.exists_in(chart) || !defending.exists_in(chart) {
+            return 1.0;
+        }
+        match (chart, self, defending) {
+            (TypeChart::Gen1, Bug, Poison) => return 2.0,
+            (TypeChart::Gen1, Poison, Bug) => return 2.0,
+            (TypeChart::Gen1, Ghost, Psychic) => return 0.0,
+            (TypeChart::Gen1, Ice, Fire) => return 1.0,
+            (TypeChart::Gen1 | TypeChart::Gen2To5, Ghost | Dark, Steel) => return 0.5,
+            _ => {}
+        }
+        match (self, defending) {
+            (Normal, Rock | Steel) => 0.5,
+            (Normal, Ghost) => 0.0,
+            (Fire, Grass | Ice | Bug | Steel) => 2.0,
+            (Fire, Fire | Water | Rock | Dragon) => 0.5,
+            (Water, Fire | Ground | Rock) => 2.0,
+            (Water, Water | Grass | Dragon) => 0.5,
+            (Electric, Water | Flying) => 2.0,
+            (Electric, Electric | Grass | Dragon) => 0.5,
+            (Electric, Ground) => 0.0,
+            (Grass, Water | Ground | Rock) => 2.0,
+            (Grass, Fire | Grass | Poison | Flying | Bug | Dragon | Steel) => 0.5,
+            (Ice, Grass | Ground | Flying | Dragon) => 2.0,
+            (Ice, Fire | Water | Ice | Steel) => 0.5,
+            (Fighting, Normal | Ice | Rock | Dark | Steel) => 2.0,
+            (Fighting, Poison | Flying | Psychic | Bug | Fairy) => 0.5,
+            (Fighting, Ghost) => 0.0,
+            (Poison, Grass | Fairy) => 2.0,
+            (Poison, Poison | Ground | Rock | Ghost) => 0.5,
+            (Poison, Steel) => 0.0,
+            (Ground, Fire | Electric | Poison | Rock | Steel) => 2.0,
+            (Ground, Grass | Bug) => 0.5,
+            (Ground, Flying) => 0.0,
+            (Flying, Grass | Fighting | Bug) => 2.0,
+            (Flying, Electric | Rock | Steel) => 0.5,
+            (Psychic, Fighting | Poison) => 2.0,
+            (Psychic, Psychic | Steel) => 0.5,
+            (Psychic, Dark) => 0.0,
+            (Bug, Grass | Psychic | Dark) => 2.0,
+            (Bug, Fire | Fighting | Poison | Flying | Ghost | Steel | Fairy) => 0.5,
+            (Rock, Fire | Ice | Flying | Bug) => 2.0,
+            (Rock, Fighting | Ground | Steel) => 0.5,
+            (Ghost, Psychic | Ghost) => 2.0,
+            (Ghost, Dark) => 0.5,
+            (Ghost, Normal) => 0.0,
+            (Dragon, Dragon) => 2.0,
+            (Dragon, Steel) => 0.5,
+            (Dragon, Fairy) => 0.0,
+            (Dark, Psychic | Ghost) => 2.0,
+            (Dark, Fighting | Dark | Fairy) => 0.5,
+            (Steel, Ice | Rock | Fairy) => 2.0,
+            (Steel, Fire | Water | Electric | Steel) => 0.5,
+            (Fairy, Fighting | Dragon | Dark) => 2.0,
+            (Fairy, Fire | Poison | Steel) => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Damage multiplier of a move of this type against a Pokémon with `defending` types
+    pub fn multiplier_against_types(self, defending: &[MonType], chart: TypeChart) -> f32 {
+        defending
+            .iter()
+            .map(|defending| self.multiplier_against(*defending, chart))
+            .product()
+    }
+}
+
+/// Abilities that change how much damage a Pokémon takes based on the move's type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefensiveAbility {
+    Levitate,
+    EarthEater,
+    FlashFire,
+    WellBakedBody,
+    WaterAbsorb,
+    StormDrain,
+    DrySkin,
+    VoltAbsorb,
+    LightningRod,
+    MotorDrive,
+    SapSipper,
+    ThickFat,
+    Heatproof,
+    WaterBubble,
+    PurifyingSalt,
+    Fluffy,
+    WonderGuard,
+    /// Also covers Solid Rock and Prism Armor, which do the same thing
+    Filter,
+}
+
+impl FromStr for DefensiveAbility {
+    type Err = ();
+
+    /// Parses PokeAPI ability names, abilities that don't affect type matchups are an error
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "levitate" => Ok(DefensiveAbility::Levitate),
+            "earth-eater" => Ok(DefensiveAbility::EarthEater),
+            "flash-fire" => Ok(DefensiveAbility::FlashFire),
+            "well-baked-body" => Ok(DefensiveAbility::WellBakedBody),
+            "water-absorb" => Ok(DefensiveAbility::WaterAbsorb),
+            "storm-drain" => Ok(DefensiveAbility::StormDrain),
+            "dry-skin" => Ok(DefensiveAbility::DrySkin),
+            "volt-absorb" => Ok(DefensiveAbility::VoltAbsorb),
+            "lightning-rod" => Ok(DefensiveAbility::LightningRod),
+            "motor-drive" => Ok(DefensiveAbility::MotorDrive),
+            "sap-sipper" => Ok(DefensiveAbility::SapSipper),
+            "thick-fat" => Ok(DefensiveAbility::ThickFat),
+            "heatproof" => Ok(DefensiveAbility::Heatproof),
+            "water-bubble" => Ok(DefensiveAbility::WaterBubble),
+            "purifying-salt" => Ok(DefensiveAbility::PurifyingSalt),
+            "fluffy" => Ok(DefensiveAbility::Fluffy),
+            "wonder-guard" => Ok(DefensiveAbility::WonderGuard),
+            "filter" | "solid-rock" | "prism-armor" => Ok(DefensiveAbility::Filter),
+            _ => Err(()),
+        }
+    }
+}
+
+impl DefensiveAbility {
+    /// Applies the ability on top of the type chart `multiplier` for a move of type `attacking`
+    pub fn modify(self, attacking: MonType, multiplier: f32) -> f32 {
+        use DefensiveAbility::*;
+        match (self, attacking) {
+            (Levitate | EarthEater, MonType::Ground) => 0.0,
+            (FlashFire | WellBakedBody, MonType::Fire) => 0.0,
+            (WaterAbsorb | StormDrain | DrySkin, MonType::Water) => 0.0,
+            (VoltAbsorb | LightningRod | MotorDrive, MonType::Electric) => 0.0,
+            (SapSipper, MonType::Grass) => 0.0,
+            (DrySkin, MonType::Fire) => multiplier * 1.25,
+            (ThickFat, MonType::Fire | MonType::Ice) => multiplier * 0.5,
+            (Heatproof | WaterBubble, MonType::Fire) => multiplier * 0.5,
+            (PurifyingSalt, MonType::Ghost) => multiplier * 0.5,
+            (Fluffy, MonType::Fire) => multiplier * 2.0,
+            (WonderGuard, _) if multiplier <= 1.0 => 0.0,
+            (Filter, _) if multiplier > 1.0 => multiplier * 0.75,
+            _ => multiplier,
+        }
+    }
+}
+
+/// How every attacking type fares against a defending Pokémon
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matchups {
+    /// Multiplier per attacking type, only types that exist on the chart
+    pub multipliers: Vec<(MonType, f32)>,
+}
+
+impl Matchups {
+    pub fn new(defending: &[MonType], chart: TypeChart, ability: Option<DefensiveAbility>) -> Self {
+        Self {
+            multipliers: chart
+                .types()
+                .map(|attacking| {
+                    let multiplier = attacking.multiplier_against_types(defending, chart);
+                    let multiplier = match ability {
+                        Some(ability) => ability.modify(attacking, multiplier),
+                        None => multiplier,
+                    };
+                    (attacking, multiplier)
+                })
+                .collect(),
+        }
+    }
+
+    /// Attacking types that deal more than normal damage, strongest first
+    pub fn weaknesses(&self) -> Vec<(MonType, f32)> {
+        let mut weaknesses: Vec<_> = self.multipliers.iter().copied().filter(|(_, m)| *m > 1.0).collect();
+        weaknesses.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        weaknesses
+    }
+
+    /// Attacking types that deal less than normal damage, weakest first
+    pub fn resistances(&self) -> Vec<(MonType, f32)> {
+        let mut resistances: Vec<_> = self
+            .multipliers
+            .iter()
+            .copied()
+            .filter(|(_, m)| *m > 0.0 && *m < 1.0)
+            .collect();
+        resistances.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        resistances
+    }
+
+    pub fn immunities(&self) -> Vec<MonType> {
+        self.multipliers
+            .iter()
+            .filter(|(_, m)| *m == 0.0)
+            .map(|(t, _)| *t)
+            .collect()
+    }
+}
+
+/// Formats a multiplier the way the games' type charts do, like `½` or `4`
+pub fn format_multiplier(multiplier: f32) -> String {
+    match multiplier {
+        0.0 => "0".to_owned(),
+        0.25 => "¼".to_owned(),
+        0.5 => "½".to_owned(),
+        m if m.fract() == 0.0 => format!("{m}"),
+        m => format!("{m:.2}").trim_end_matches('0').to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MonType::*;
+
+    impl Matchups {
+        fn multiplier(&self, attacking: MonType) -> f32 {
+            self.multipliers
+                .iter()
+                .find(|(t, _)| *t == attacking)
+                .map_or(1.0, |(_, multiplier)| *multiplier)
+        }
+    }
+
+    #[test]
+    fn single_type_multipliers() {
+        let chart = TypeChart::Gen6On;
+        assert_eq!(Fire.multiplier_against(Grass, chart), 2.0);
+        assert_eq!(Fire.multiplier_against(Water, chart), 0.5);
+        assert_eq!(Normal.multiplier_against(Ghost, chart), 0.0);
+        assert_eq!(Dragon.multiplier_against(Fairy, chart), 0.0);
+        assert_eq!(Electric.multiplier_against(Normal, chart), 1.0);
+    }
+
+    #[test]
+    fn every_chart_has_the_expected_immunities() {
+        let immunities = |chart: TypeChart| {
+            chart
+                .types()
+                .flat_map(|a| chart.types().map(move |d| (a, d)))
+                .filter(|(a, d)| a.multiplier_against(*d, chart) == 0.0)
+                .count()
+        };
+        // Gen 1: Normal/Fighting->Ghost, Ghost->Normal, Ghost->Psychic, Electric->Ground, Ground->Flying
+        assert_eq!(immunities(TypeChart::Gen1), 6);
+        // Gen 2: Psychic is no longer immune to Ghost, adds Psychic->Dark and Poison->Steel
+        assert_eq!(immunities(TypeChart::Gen2To5), 7);
+        // Gen 6: adds Dragon->Fairy
+        assert_eq!(immunities(TypeChart::Gen6On), 8);
+    }
+
+    #[test]
+    fn generation_differences() {
+        assert_eq!(Ghost.multiplier_against(Psychic, TypeChart::Gen1), 0.0);
+        assert_eq!(Ghost.multiplier_against(Psychic, TypeChart::Gen2To5), 2.0);
+        assert_eq!(Bug.multiplier_against(Poison, TypeChart::Gen1), 2.0);
+        assert_eq!(Bug.multiplier_against(Poison, TypeChart::Gen6On), 0.5);
+        assert_eq!(Poison.multiplier_against(Bug, TypeChart::Gen1), 2.0);
+        assert_eq!(Poison.multiplier_against(Bug, TypeChart::Gen2To5), 1.0);
+        assert_eq!(Ice.multiplier_against(Fire, TypeChart::Gen1), 1.0);
+        assert_eq!(Ice.multiplier_against(Fire, TypeChart::Gen2To5), 0.5);
+        assert_eq!(Dark.multiplier_against(Steel, TypeChart::Gen2To5), 0.5);
+        assert_eq!(Dark.multiplier_against(Steel, TypeChart::Gen6On), 1.0);
+        assert_eq!(Ghost.multiplier_against(Steel, TypeChart::Gen6On), 1.0);
+    }
+
+    #[test]
+    fn charts_cycle() {
+        assert_eq!(TypeChart::Gen1.next(), TypeChart::Gen2To5);
+        assert_eq!(TypeChart::Gen6On.next(), TypeChart::Gen1);
+    }
+
+    #[test]
+    fn types_missing_from_the_chart_are_neutral() {
+        assert_eq!(Fairy.multiplier_against(Dragon, TypeChart::Gen2To5), 1.0);
+        assert_eq!(Fighting.multiplier_against(Steel, TypeChart::Gen1), 1.0);
+        assert_eq!(TypeChart::Gen1.types().count(), 15);
+        assert_eq!(TypeChart::Gen2To5.types().count(), 17);
+        assert_eq!(TypeChart::Gen6On.types().count(), 18);
+    }
+
+    #[test]
+    fn dual_types_multiply() {
+        let chart = TypeChart::Gen6On;
+        assert_eq!(Ice.multiplier_against_types(&[Dragon, Ground], chart), 4.0);
+        assert_eq!(Fire.multiplier_against_types(&[Water, Rock], chart), 0.25);
+        assert_eq!(Ground.multiplier_against_types(&[Fire, Flying], chart), 0.0);
+        assert_eq!(Water.multiplier_against_types(&[Fire, Water], chart), 1.0);
+    }
+
+    #[test]
+    fn matchups_for_a_dual_type() {
+        // Gengar without an ability, it had Levitate until Gen 7
+        let matchups = Matchups::new(&[Ghost, Poison], TypeChart::Gen6On, None);
+        assert_eq!(matchups.immunities(), vec![Normal, Fighting]);
+        assert_eq!(
+            matchups.weaknesses(),
+            vec![(Ground, 2.0), (Psychic, 2.0), (Ghost, 2.0), (Dark, 2.0)]
+        );
+        assert_eq!(matchups.resistances()[..2], [(Poison, 0.25), (Bug, 0.25)]);
+        assert_eq!(matchups.multiplier(Poison), 0.25);
+        assert_eq!(matchups.multiplier(Grass), 0.5);
+        assert_eq!(matchups.multiplier(Fairy), 0.5);
+    }
+
+    #[test]
+    fn ability_overrides() {
+        let chart = TypeChart::Gen6On;
+        let levitate = Matchups::new(&[Ghost, Poison], chart, Some(DefensiveAbility::Levitate));
+        assert_eq!(levitate.multiplier(Ground), 0.0);
+        assert!(levitate.immunities().contains(&Ground));
+
+        let flash_fire = Matchups::new(&[Fire], chart, Some(DefensiveAbility::FlashFire));
+        assert_eq!(flash_fire.multiplier(Fire), 0.0);
+
+        // Shedinja only takes super effective hits
+        let wonder_guard = Matchups::new(&[Bug, Ghost], chart, Some(DefensiveAbility::WonderGuard));
+        assert_eq!(
+            wonder_guard.weaknesses().iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+            vec![Fire, Flying, Rock, Ghost, Dark]
+        );
+        assert!(wonder_guard.resistances().is_empty());
+
+        let thick_fat = Matchups::new(&[Grass], chart, Some(DefensiveAbility::ThickFat));
+        assert_eq!(thick_fat.multiplier(Fire), 1.0);
+        assert_eq!(thick_fat.multiplier(Ice), 1.0);
+
+        let dry_skin = Matchups::new(&[Poison, Fighting], chart, Some(DefensiveAbility::DrySkin));
+        assert_eq!(dry_skin.multiplier(Fire), 1.25);
+        assert_eq!(dry_skin.multiplier(Water), 0.0);
+
+        let solid_rock = Matchups::new(&[Rock, Ground], chart, Some(DefensiveAbility::Filter));
+        assert_eq!(solid_rock.multiplier(Water), 3.0);
+        assert_eq!(solid_rock.multiplier(Normal), 0.5);
+    }
+
+    #[test]
+    fn ability_names_parse() {
+        assert_eq!("levitate".parse(), Ok(DefensiveAbility::Levitate));
+        assert_eq!("prism-armor".parse(), Ok(DefensiveAbility::Filter));
+        assert_eq!("overgrow".parse::<DefensiveAbility>(), Err(()));
+    }
+
+    #[test]
+    fn multiplier_formatting() {
+        assert_eq!(format_multiplier(0.0), "0");
+        assert_eq!(format_multiplier(0.25), "¼");
+        assert_eq!(format_multiplier(0.5), "½");
+        assert_eq!(format_multiplier(4.0), "4");
+        assert_eq!(format_multiplier(1.25), "1.25");
+        assert_eq!(format_multiplier(1.5), "1.5");
+    }
+
+    fn types(names: &[&str]) -> Vec<PokemonType> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                serde_json::from_value(serde_json::json!({ "slot": i + 1, "type": { "name": name, "url": "" } })).unwrap()
+            })
+            .collect()
+    }
+
+    fn past(generation: &str, names: &[&str]) -> PokemonTypePast {
+        PokemonTypePast {
+            generation: serde_json::from_value(serde_json::json!({ "name": generation, "url": "" })).unwrap(),
+            types: types(names),
+        }
+    }
+
+    #[test]
+    fn older_charts_use_past_types() {
+        // Clefairy was Normal up to Gen 5
+        let clefairy = (types(&["fairy"]), vec![past("generation-v", &["normal"])]);
+        let under = |chart| types_under(&clefairy.0, &clefairy.1, chart);
+        assert_eq!(under(TypeChart::Gen1), [Normal]);
+        assert_eq!(under(TypeChart::Gen2To5), [Normal]);
+        assert_eq!(under(TypeChart::Gen6On), [Fairy]);
+
+        // Magnemite only became Steel in Gen 2, when the type was added
+        let magnemite = (types(&["electric", "steel"]), vec![past("generation-i", &["electric"])]);
+        let under = |chart| types_under(&magnemite.0, &magnemite.1, chart);
+        assert_eq!(under(TypeChart::Gen1), [Electric]);
+        assert_eq!(under(TypeChart::Gen2To5), [Electric, Steel]);
+
+        assert_eq!(types_under(&types(&["ghost", "poison"]), &[], TypeChart::Gen1), [Ghost, Poison]);
+    }
+}
@@ -0,0 +1,249 @@
+use rustemon::model::evolution::{ChainLink, EvolutionDetail};
+
+/// One species of an evolution chain, flattened so the tree can be drawn as a list
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvolutionStep {
+    /// 0 for the first stage, children are one deeper than their parent
+    pub depth: usize,
+    pub species: String,
+    /// How the previous stage evolves into this one, empty for the first stage
+    pub conditions: String,
+}
+
+/// Depth-first walk of the chain, branches stay right under the stage they evolve from
+pub fn flatten(chain: &ChainLink) -> Vec<EvolutionStep> {
+    fn walk(link: &ChainLink, depth: usize, steps: &mut Vec<EvolutionStep>) {
+        let mut conditions: Vec<String> = link.evolution_details.iter().map(describe).collect();
+        conditions.dedup();
+        steps.push(EvolutionStep {
+            depth,
+            species: link.species.name.clone(),
+            conditions: conditions.join(" or "),
+        });
+        for next in &link.evolves_to {
+            walk(next, depth + 1, steps);
+        }
+    }
+    let mut steps = Vec::new();
+    walk(chain, 0, &mut steps);
+    steps
+}
+
+/// Human readable trigger and requirements, like "Lv 16" or "Use water-stone"
+pub fn describe(detail: &EvolutionDetail) -> String {
+    let name = |name: &str| name.replace('-', " ");
+    let mut parts = Vec::new();
+    match (detail.trigger.name.as_str(), detail.min_level) {
+        ("level-up", Some(level)) => parts.push(format!("Lv {level}")),
+        ("level-up", None) => parts.push("Level up".to_owned()),
+        ("use-item", _) => parts.push(format!(
+            "Use {}",
+            detail.item.as_ref().map_or("item".to_owned(), |item| name(&item.name))
+        )),
+        ("trade", _) => parts.push("Trade".to_owned()),
+        ("shed", _) => parts.push("Lv 20 with a free party slot and a Poké Ball".to_owned()),
+        (trigger, Some(level)) => parts.push(format!("{} at Lv {level}", name(trigger))),
+        (trigger, None) => parts.push(name(trigger)),
+    }
+    if let Some(item) = &detail.held_item {
+        parts.push(format!("holding {}", name(&item.name)));
+    }
+    if let Some(species) = &detail.trade_species {
+        parts.push(format!("for {}", name(&species.name)));
+    }
+    if let Some(move_) = &detail.known_move {
+        parts.push(format!("knowing {}", name(&move_.name)));
+    }
+    if let Some(type_) = &detail.known_move_type {
+        parts.push(format!("knowing a {} move", type_.name));
+    }
+    if let Some(location) = &detail.location {
+        parts.push(format!("at {}", name(&location.name)));
+    }
+    if let Some(happiness) = detail.min_happiness {
+        parts.push(format!("friendship {happiness}+"));
+    }
+    if let Some(beauty) = detail.min_beauty {
+        parts.push(format!("beauty {beauty}+"));
+    }
+    if let Some(affection) = detail.min_affection {
+        parts.push(format!("affection {affection}+"));
+    }
+    match detail.relative_physical_stats {
+        Some(1) => parts.push("Atk > Def".to_owned()),
+        Some(0) => parts.push("Atk = Def".to_owned()),
+        Some(-1) => parts.push("Atk < Def".to_owned()),
+        _ => {}
+    }
+    if let Some(species) = &detail.party_species {
+        parts.push(format!("with {} in the party", name(&species.name)));
+    }
+    if let Some(type_) = &detail.party_type {
+        parts.push(format!("with a {} type in the party", type_.name));
+    }
+    match detail.gender {
+        Some(1) => parts.push("female".to_owned()),
+        Some(2) => parts.push("male".to_owned()),
+        _ => {}
+    }
+    if !detail.time_of_day.is_empty() {
+        parts.push(format!("during the {}", detail.time_of_day));
+    }
+    if detail.needs_overworld_rain {
+        parts.push("while raining".to_owned());
+    }
+    if detail.turn_upside_down {
+        parts.push("holding the console upside down".to_owned());
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    /// `trigger` with the requirements in `fields`, everything else unset
+    fn detail(trigger: &str, fields: Value) -> EvolutionDetail {
+        let mut detail = json!({
+            "trigger": { "name": trigger, "url": "" },
+            "version_group": { "name": "scarlet-violet", "url": "" },
+            "is_default": true,
+            "needs_overworld_rain": false,
+            "needs_multiplayer": false,
+            "near_special_rock": false,
+            "time_of_day": "",
+            "turn_upside_down": false,
+        });
+        detail.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(detail).unwrap()
+    }
+
+    fn named(name: &str) -> Value {
+        json!({ "name": name, "url": "" })
+    }
+
+    fn link(species: &str, details: Vec<EvolutionDetail>, evolves_to: Vec<ChainLink>) -> ChainLink {
+        ChainLink {
+            is_baby: false,
+            species: serde_json::from_value(named(species)).unwrap(),
+            evolution_details: details,
+            evolves_to,
+        }
+    }
+
+    fn level(level: i64) -> EvolutionDetail {
+        detail("level-up", json!({ "min_level": level }))
+    }
+
+    fn step(depth: usize, species: &str, conditions: &str) -> EvolutionStep {
+        EvolutionStep {
+            depth,
+            species: species.to_owned(),
+            conditions: conditions.to_owned(),
+        }
+    }
+
+    #[test]
+    fn eevee_branches_stay_under_eevee() {
+        let eevee = link(
+            "eevee",
+            vec![],
+            vec![
+                link("vaporeon", vec![detail("use-item", json!({ "item": named("water-stone") }))], vec![]),
+                link(
+                    "espeon",
+                    vec![detail("level-up", json!({ "min_happiness": 160, "time_of_day": "day" }))],
+                    vec![],
+                ),
+                link(
+                    "leafeon",
+                    vec![
+                        detail("level-up", json!({ "location": named("eterna-forest") })),
+                        detail("level-up", json!({ "location": named("pinwheel-forest") })),
+                        detail("use-item", json!({ "item": named("leaf-stone") })),
+                    ],
+                    vec![],
+                ),
+                link(
+                    "sylveon",
+                    vec![detail("level-up", json!({ "min_affection": 2, "known_move_type": named("fairy") }))],
+                    vec![],
+                ),
+            ],
+        );
+        assert_eq!(
+            flatten(&eevee),
+            [
+                step(0, "eevee", ""),
+                step(1, "vaporeon", "Use water stone"),
+                step(1, "espeon", "Level up, friendship 160+, during the day"),
+                step(
+                    1,
+                    "leafeon",
+                    "Level up, at eterna forest or Level up, at pinwheel forest or Use leaf stone"
+                ),
+                step(1, "sylveon", "Level up, knowing a fairy move, affection 2+"),
+            ]
+        );
+    }
+
+    #[test]
+    fn wurmple_branches_are_walked_depth_first() {
+        let wurmple = link(
+            "wurmple",
+            vec![],
+            vec![
+                link("silcoon", vec![level(7)], vec![link("beautifly", vec![level(10)], vec![])]),
+                link("cascoon", vec![level(7)], vec![link("dustox", vec![level(10)], vec![])]),
+            ],
+        );
+        assert_eq!(
+            flatten(&wurmple),
+            [
+                step(0, "wurmple", ""),
+                step(1, "silcoon", "Lv 7"),
+                step(2, "beautifly", "Lv 10"),
+                step(1, "cascoon", "Lv 7"),
+                step(2, "dustox", "Lv 10"),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_conditions_are_shown_once() {
+        // PokeAPI lists some evolutions once per game that has them
+        let chain = link("charmander", vec![], vec![link("charmeleon", vec![level(16), level(16)], vec![])]);
+        assert_eq!(flatten(&chain)[1].conditions, "Lv 16");
+    }
+
+    #[test]
+    fn trigger_wording() {
+        assert_eq!(describe(&level(16)), "Lv 16");
+        assert_eq!(describe(&detail("trade", json!({ "held_item": named("metal-coat") }))), "Trade, holding metal coat");
+        assert_eq!(describe(&detail("trade", json!({ "trade_species": named("shelmet") }))), "Trade, for shelmet");
+        assert_eq!(describe(&detail("shed", json!({}))), "Lv 20 with a free party slot and a Poké Ball");
+        assert_eq!(describe(&detail("three-critical-hits", json!({}))), "three critical hits");
+        assert_eq!(describe(&detail("recoil-damage", json!({ "min_level": 30 }))), "recoil damage at Lv 30");
+        assert_eq!(
+            describe(&detail("level-up", json!({ "min_level": 20, "relative_physical_stats": 1 }))),
+            "Lv 20, Atk > Def"
+        );
+        assert_eq!(
+            describe(&detail("level-up", json!({ "min_level": 30, "turn_upside_down": true }))),
+            "Lv 30, holding the console upside down"
+        );
+        assert_eq!(
+            describe(&detail("level-up", json!({ "min_level": 50, "needs_overworld_rain": true }))),
+            "Lv 50, while raining"
+        );
+        assert_eq!(
+            describe(&detail("use-item", json!({ "item": named("dawn-stone"), "gender": 1 }))),
+            "Use dawn stone, female"
+        );
+        assert_eq!(
+            describe(&detail("level-up", json!({ "party_species": named("remoraid") }))),
+            "Level up, with remoraid in the party"
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::{fmt, str::FromStr};
+
+use rustemon::model::pokemon::{Pokemon, PokemonSpecies};
+
+use crate::fetch::{FetchError, Priority, Scheduler};
+
+use super::{fetch_pokemon_or_species, stats::base_stats, MonStat, MonType};
+
+/// What the structured filters look at for one species, from its species entry and default form
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexEntry {
+    pub types: Vec<MonType>,
+    pub stats: [u32; 6],
+    pub abilities: Vec<String>,
+    pub moves: Vec<String>,
+    /// Generation the species was introduced in, 0 when PokeAPI doesn't say
+    pub generation: u8,
+}
+
+impl IndexEntry {
+    pub fn new(species: &PokemonSpecies, mon: &Pokemon) -> Self {
+        Self {
+            types: mon.types.iter().filter_map(|t| t.type_.name.parse().ok()).collect(),
+            stats: base_stats(&mon.stats),
+            abilities: mon.abilities.iter().flat_map(|a| &a.ability).map(|a| a.name.clone()).collect(),
+            moves: mon.moves.iter().map(|m| m.move_.name.clone()).collect(),
+            generation: generation_number(&species.generation.name).unwrap_or(0),
+        }
+    }
+
+    pub fn stat(&self, key: StatKey) -> u32 {
+        match key {
+            StatKey::Stat(stat) => self.stats[MonStat::ALL.iter().position(|s| *s == stat).unwrap_or(0)],
+            StatKey::Total => self.stats.iter().sum(),
+        }
+    }
+}
+
+/// Loads what the index needs for a species, both requests go through the response cache
+pub async fn fetch_index_entry(species: &str, scheduler: &Scheduler, priority: Priority) -> Result<IndexEntry, FetchError> {
+    let entry = scheduler.species(species, priority).await?;
+    let mon = fetch_pokemon_or_species(species, scheduler, priority).await?;
+    Ok(IndexEntry::new(&entry, &mon))
+}
+
+/// "generation-iv" to 4
+pub fn generation_number(name: &str) -> Option<u8> {
+    let numeral = name.strip_prefix("generation-")?;
+    const NUMERALS: [&str; 9] = ["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"];
+    NUMERALS.iter().position(|n| *n == numeral).map(|i| i as u8 + 1)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, value: u32, threshold: u32) -> bool {
+        match self {
+            Comparison::Less => value < threshold,
+            Comparison::LessOrEqual => value <= threshold,
+            Comparison::Equal => value == threshold,
+            Comparison::GreaterOrEqual => value >= threshold,
+            Comparison::Greater => value > threshold,
+        }
+    }
+}
+
+/// A stat, or all of them added up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatKey {
+    Stat(MonStat),
+    Total,
+}
+
+impl StatKey {
+    /// Total first, then the stats in the order the games list them
+    pub const ALL: [StatKey; 7] = [
+        StatKey::Total,
+        StatKey::Stat(MonStat::HP),
+        StatKey::Stat(MonStat::Attack),
+        StatKey::Stat(MonStat::Defense),
+        StatKey::Stat(MonStat::SpecialAttack),
+        StatKey::Stat(MonStat::SpecialDefense),
+        StatKey::Stat(MonStat::Speed),
+    ];
+
+    pub fn to_short_string(self) -> String {
+        match self {
+            StatKey::Stat(stat) => stat.to_short_string(),
+            StatKey::Total => "BST".to_owned(),
+        }
+    }
+}
+
+impl FromStr for StatKey {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hp" => Ok(StatKey::Stat(MonStat::HP)),
+            "atk" => Ok(StatKey::Stat(MonStat::Attack)),
+            "def" => Ok(StatKey::Stat(MonStat::Defense)),
+            "spa" => Ok(StatKey::Stat(MonStat::SpecialAttack)),
+            "spd" => Ok(StatKey::Stat(MonStat::SpecialDefense)),
+            "spe" => Ok(StatKey::Stat(MonStat::Speed)),
+            "bst" | "total" => Ok(StatKey::Total),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One `key:value` or `stat>=value` term, every term has to hold for a species to be shown
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Type(MonType),
+    Stat(StatKey, Comparison, u32),
+    Ability(String),
+    Move(String),
+    Generation(u8),
+}
+
+impl Condition {
+    pub fn holds(&self, entry: &IndexEntry) -> bool {
+        match self {
+            Condition::Type(type_) => entry.types.contains(type_),
+            Condition::Stat(key, comparison, threshold) => comparison.holds(entry.stat(*key), *threshold),
+            Condition::Ability(ability) => entry.abilities.contains(ability),
+            Condition::Move(move_) => entry.moves.contains(move_),
+            Condition::Generation(generation) => entry.generation == *generation,
+        }
+    }
+}
+
+/// The search box split into the fuzzy name part and the structured terms
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    pub name: String,
+    pub conditions: Vec<Condition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError(String);
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Query {
+    pub fn matches(&self, entry: &IndexEntry) -> bool {
+        self.conditions.iter().all(|condition| condition.holds(entry))
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    /// Words with a `:` or a comparison are terms, like `type:fire spe>=100 move:u_turn`, everything
+    /// else is the name. Underscores stand in for spaces since words are split on them
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut query = Query::default();
+        let mut name = Vec::new();
+        for word in s.split_whitespace() {
+            let word = word.to_lowercase();
+            if let Some((key, value)) = word.split_once(':') {
+                let value = value.replace('_', "-");
+                let condition = match key {
+                    "type" => value.parse().map(Condition::Type).map_err(|_| QueryError(format!("unknown type \"{value}\"")))?,
+                    "ability" => Condition::Ability(value),
+                    "move" => Condition::Move(value),
+                    "gen" => match value.parse() {
+                        Ok(generation @ 1..=9) => Condition::Generation(generation),
+                        _ => return Err(QueryError(format!("\"{value}\" is not a generation"))),
+                    },
+                    _ => return Err(QueryError(format!("unknown filter \"{key}\", try type, ability, move or gen"))),
+                };
+                query.conditions.push(condition);
+            } else if let Some(index) = word.find(['<', '>', '=']) {
+                query.conditions.push(parse_stat_condition(&word[..index], &word[index..])?);
+            } else {
+                name.push(word);
+            }
+        }
+        query.name = name.join(" ");
+        Ok(query)
+    }
+}
+
+/// `stat` is the part before the comparison, `rest` the comparison and the value
+fn parse_stat_condition(stat: &str, rest: &str) -> Result<Condition, QueryError> {
+    let key = stat
+        .parse()
+        .map_err(|_| QueryError(format!("unknown stat \"{stat}\", try hp, atk, def, spa, spd, spe or bst")))?;
+    let (comparison, value) = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        ("=", Comparison::Equal),
+    ]
+    .into_iter()
+    .find_map(|(symbol, comparison)| rest.strip_prefix(symbol).map(|value| (comparison, value)))
+    .ok_or_else(|| QueryError(format!("can't read \"{stat}{rest}\"")))?;
+    let value = value.parse().map_err(|_| QueryError(format!("\"{value}\" is not a number")))?;
+    Ok(Condition::Stat(key, comparison, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gliscor() -> IndexEntry {
+        IndexEntry {
+            types: vec![MonType::Ground, MonType::Flying],
+            stats: [75, 95, 125, 45, 75, 95],
+            abilities: vec!["hyper-cutter".to_owned(), "sand-veil".to_owned(), "poison-heal".to_owned()],
+            moves: vec!["earthquake".to_owned(), "u-turn".to_owned()],
+            generation: 4,
+        }
+    }
+
+    #[test]
+    fn parses_terms_and_name() {
+        let query: Query = "Type:ground glis spe>=90 ability:poison_heal gen:4".parse().unwrap();
+        assert_eq!(query.name, "glis");
+        assert_eq!(
+            query.conditions,
+            [
+                Condition::Type(MonType::Ground),
+                Condition::Stat(StatKey::Stat(MonStat::Speed), Comparison::GreaterOrEqual, 90),
+                Condition::Ability("poison-heal".to_owned()),
+                Condition::Generation(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn every_condition_has_to_hold() {
+        let entry = gliscor();
+        let matches = |query: &str| query.parse::<Query>().unwrap().matches(&entry);
+        assert!(matches("type:ground type:flying"));
+        assert!(!matches("type:ground type:fire"));
+        assert!(matches("spe>=95 spe<=95 spe=95 def>100 spa<46"));
+        assert!(!matches("spe>95"));
+        assert!(matches("bst=510"));
+        assert!(matches("move:u_turn move:earthquake ability:sand_veil gen:4"));
+        assert!(!matches("move:surf"));
+        assert!(!matches("gen:5"));
+        assert!(matches(""));
+    }
+
+    #[test]
+    fn reports_bad_terms() {
+        let error = |query: &str| query.parse::<Query>().unwrap_err().to_string();
+        assert_eq!(error("type:sound"), "unknown type \"sound\"");
+        assert_eq!(error("gen:10"), "\"10\" is not a generation");
+        assert_eq!(error("speed>=100"), "unknown stat \"speed\", try hp, atk, def, spa, spd, spe or bst");
+        assert_eq!(error("spe>=fast"), "\"fast\" is not a number");
+        assert_eq!(error("colour:red"), "unknown filter \"colour\", try type, ability, move or gen");
+    }
+
+    #[test]
+    fn generations() {
+        assert_eq!(generation_number("generation-iv"), Some(4));
+        assert_eq!(generation_number("generation-ix"), Some(9));
+        assert_eq!(generation_number("kanto"), None);
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+
+use rustemon::model::pokemon::PokemonMove;
+
+/// Version groups from oldest to newest with the versions in each, PokeAPI only exposes this
+/// through extra requests
+const VERSION_GROUPS: [(&str, &[&str]); 27] = [
+    ("red-blue", &["red", "blue"]),
+    ("yellow", &["yellow"]),
+    ("gold-silver", &["gold", "silver"]),
+    ("crystal", &["crystal"]),
+    ("ruby-sapphire", &["ruby", "sapphire"]),
+    ("emerald", &["emerald"]),
+    ("firered-leafgreen", &["firered", "leafgreen"]),
+    ("colosseum", &["colosseum"]),
+    ("xd", &["xd"]),
+    ("diamond-pearl", &["diamond", "pearl"]),
+    ("platinum", &["platinum"]),
+    ("heartgold-soulsilver", &["heartgold", "soulsilver"]),
+    ("black-white", &["black", "white"]),
+    ("black-2-white-2", &["black-2", "white-2"]),
+    ("x-y", &["x", "y"]),
+    ("omega-ruby-alpha-sapphire", &["omega-ruby", "alpha-sapphire"]),
+    ("sun-moon", &["sun", "moon"]),
+    ("ultra-sun-ultra-moon", &["ultra-sun", "ultra-moon"]),
+    ("lets-go-pikachu-lets-go-eevee", &["lets-go-pikachu", "lets-go-eevee"]),
+    ("sword-shield", &["sword", "shield"]),
+    ("the-isle-of-armor", &["sword", "shield"]),
+    ("the-crown-tundra", &["sword", "shield"]),
+    ("brilliant-diamond-and-shining-pearl", &["brilliant-diamond", "shining-pearl"]),
+    ("legends-arceus", &["legends-arceus"]),
+    ("scarlet-violet", &["scarlet", "violet"]),
+    ("the-teal-mask", &["scarlet", "violet"]),
+    ("the-indigo-disk", &["scarlet", "violet"]),
+];
+
+/// How a move is learned, in the order the groups are listed
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LearnMethod {
+    LevelUp,
+    Machine,
+    Egg,
+    Tutor,
+    Other(String),
+}
+
+impl From<&str> for LearnMethod {
+    fn from(name: &str) -> Self {
+        match name {
+            "level-up" => LearnMethod::LevelUp,
+            "machine" => LearnMethod::Machine,
+            "egg" => LearnMethod::Egg,
+            "tutor" => LearnMethod::Tutor,
+            other => LearnMethod::Other(other.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for LearnMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LearnMethod::LevelUp => write!(f, "Level-up"),
+            LearnMethod::Machine => write!(f, "TM/Machine"),
+            LearnMethod::Egg => write!(f, "Egg"),
+            LearnMethod::Tutor => write!(f, "Tutor"),
+            LearnMethod::Other(name) => write!(f, "{}", name.replace('-', " ")),
+        }
+    }
+}
+
+/// A move as learned in one version group, a move learned in several ways shows up once per way
+#[derive(Debug, Clone)]
+pub struct LearnedMove {
+    pub method: LearnMethod,
+    pub level: i64,
+    pub move_: PokemonMove,
+}
+
+/// Every version group the Pokémon learns moves in, oldest first. Groups this doesn't know about
+/// can't be placed, so they go before all the others
+pub fn version_groups(moves: &[PokemonMove]) -> Vec<String> {
+    let mut groups: Vec<String> = moves
+        .iter()
+        .flat_map(|move_| move_.version_group_details.iter())
+        .map(|detail| detail.version_group.name.clone())
+        .collect();
+    groups.sort_by_key(|group| {
+        let position = VERSION_GROUPS.iter().position(|(known, _)| known == group);
+        (position.map_or(0, |position| position + 1), group.clone())
+    });
+    groups.dedup();
+    groups
+}
+
+/// The newest of `version_groups` this knows about, or the last of them if it knows none
+pub fn newest_version_group(version_groups: &[String]) -> Option<&String> {
+    version_groups
+        .iter()
+        .rfind(|group| VERSION_GROUPS.iter().any(|(known, _)| known == group))
+        .or(version_groups.last())
+}
+
+/// Versions released as `version_group`, empty for version groups this doesn't know about
+pub fn versions(version_group: &str) -> &'static [&'static str] {
+    VERSION_GROUPS
+        .iter()
+        .find(|(group, _)| *group == version_group)
+        .map_or(&[], |(_, versions)| versions)
+}
+
+/// Moves learnable in `version_group`, grouped by method with level-up moves sorted by level
+pub fn learnset(moves: &[PokemonMove], version_group: &str) -> Vec<LearnedMove> {
+    let mut learnset: Vec<LearnedMove> = moves
+        .iter()
+        .flat_map(|move_| {
+            move_
+                .version_group_details
+                .iter()
+                .filter(|detail| detail.version_group.name == version_group)
+                .map(|detail| LearnedMove {
+                    method: LearnMethod::from(detail.move_learn_method.name.as_str()),
+                    level: detail.level_learned_at,
+                    move_: move_.clone(),
+                })
+        })
+        .collect();
+    learnset.sort_by(|a, b| {
+        (&a.method, a.level, &a.move_.move_.name).cmp(&(&b.method, b.level, &b.move_.move_.name))
+    });
+    learnset.dedup_by(|a, b| a.method == b.method && a.level == b.level && a.move_.move_.name == b.move_.move_.name);
+    learnset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A move with its (method, level, version group) details
+    fn move_(name: &str, details: &[(&str, i64, &str)]) -> PokemonMove {
+        let details: Vec<_> = details
+            .iter()
+            .map(|(method, level, group)| {
+                serde_json::json!({
+                    "level_learned_at": level,
+                    "move_learn_method": { "name": method, "url": "" },
+                    "version_group": { "name": group, "url": "" },
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "move": { "name": name, "url": "" },
+            "version_group_details": details,
+        }))
+        .unwrap()
+    }
+
+    fn moves() -> Vec<PokemonMove> {
+        vec![
+            move_("thunderbolt", &[("machine", 0, "red-blue"), ("machine", 0, "scarlet-violet")]),
+            move_("thunder-shock", &[("level-up", 1, "red-blue"), ("level-up", 1, "scarlet-violet")]),
+            move_("volt-tackle", &[("egg", 0, "scarlet-violet")]),
+            move_("thunder", &[("level-up", 43, "red-blue"), ("level-up", 30, "scarlet-violet")]),
+            move_("surf", &[("stadium-surfing-pikachu", 0, "yellow")]),
+            move_("grass-knot", &[("machine", 0, "scarlet-violet"), ("tutor", 0, "scarlet-violet")]),
+            // PokeAPI sometimes repeats a detail
+            move_("nuzzle", &[("level-up", 1, "scarlet-violet"), ("level-up", 1, "scarlet-violet")]),
+        ]
+    }
+
+    fn names(learnset: &[LearnedMove]) -> Vec<(LearnMethod, i64, &str)> {
+        learnset
+            .iter()
+            .map(|learned| (learned.method.clone(), learned.level, learned.move_.move_.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn version_groups_are_oldest_first() {
+        assert_eq!(version_groups(&moves()), ["red-blue", "yellow", "scarlet-violet"]);
+        let unknown = [move_("tackle", &[("level-up", 1, "z-game"), ("level-up", 1, "gold-silver")])];
+        // Groups this doesn't know about go first
+        assert_eq!(version_groups(&unknown), ["z-game", "gold-silver"]);
+        assert!(version_groups(&[]).is_empty());
+    }
+
+    #[test]
+    fn newest_version_group_skips_unknown_groups() {
+        let groups = |groups: &[&str]| groups.iter().map(|group| group.to_string()).collect::<Vec<_>>();
+        let newest = groups(&["z-game", "gold-silver", "scarlet-violet"]);
+        assert_eq!(newest_version_group(&newest).unwrap(), "scarlet-violet");
+        assert_eq!(newest_version_group(&groups(&["z-game"])).unwrap(), "z-game");
+        assert_eq!(newest_version_group(&[]), None);
+    }
+
+    #[test]
+    fn learnset_only_has_the_version_group() {
+        use LearnMethod::*;
+        assert_eq!(
+            names(&learnset(&moves(), "red-blue")),
+            [(LevelUp, 1, "thunder-shock"), (LevelUp, 43, "thunder"), (Machine, 0, "thunderbolt")]
+        );
+        assert_eq!(
+            names(&learnset(&moves(), "yellow")),
+            [(Other("stadium-surfing-pikachu".to_owned()), 0, "surf")]
+        );
+        assert!(learnset(&moves(), "gold-silver").is_empty());
+    }
+
+    #[test]
+    fn learnset_is_grouped_by_method_then_level() {
+        use LearnMethod::*;
+        // Grass Knot is listed once per way it is learned, Nuzzle only once
+        assert_eq!(
+            names(&learnset(&moves(), "scarlet-violet")),
+            [
+                (LevelUp, 1, "nuzzle"),
+                (LevelUp, 1, "thunder-shock"),
+                (LevelUp, 30, "thunder"),
+                (Machine, 0, "grass-knot"),
+                (Machine, 0, "thunderbolt"),
+                (Egg, 0, "volt-tackle"),
+                (Tutor, 0, "grass-knot"),
+            ]
+        );
+    }
+}
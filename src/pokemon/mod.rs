@@ -1,27 +1,43 @@
-use std::{str::FromStr, time::Duration};
+use std::{fmt, str::FromStr};
 
 use ratatui::style::Color;
-use rustemon::client::{CACacheManager, CacheMode, CacheOptions, RustemonClient, RustemonClientBuilder};
+use rustemon::model::pokemon::Pokemon;
+
+use crate::fetch::{FetchError, Priority, Scheduler};
+
+pub mod compare;
+pub mod damage;
+pub mod effectiveness;
+pub mod evolution;
+pub mod filter;
+pub mod learnset;
+pub mod nature;
+pub mod showdown;
+pub mod stats;
+pub mod team;
 
 pub type PokemonName= String;
-pub type AbilityName = String;
-pub type PokemonMove = String;
 
-pub fn get_client()-> RustemonClient
-{
-    // RustemonClientBuilder::default()
-    // .with_mode(CacheMode::ForceCache)
-    // .with_manager(CACacheManager::default())
-    // .with_options(CacheOptions {
-    //     shared: true,
-    //     cache_heuristic: 0.2,
-    //     immutable_min_time_to_live: Duration::from_secs(3600),
-    //     ignore_cargo_cult: true,
-    // })
-    // .with_environment(rustemon::client::Environment::Custom("http://127.0.0.1:8000/api/v2".parse().unwrap())).try_build().unwrap()
-    RustemonClient::default()
+/// Fetches a Pokémon, or the default variety when `name` is a species like deoxys that only exists
+/// as deoxys-normal and friends
+pub async fn fetch_pokemon_or_species(
+    name: &str,
+    scheduler: &Scheduler,
+    priority: Priority,
+) -> Result<Pokemon, FetchError> {
+    let error = match scheduler.pokemon(name, priority).await {
+        Ok(mon) => return Ok(mon),
+        Err(err) => err,
+    };
+    let species = scheduler.species(name, priority).await.map_err(|_| error.clone())?;
+    let variety = species.varieties.iter().find(|variety| variety.is_default).or(species.varieties.first());
+    match variety {
+        Some(variety) => scheduler.pokemon(&variety.pokemon.name, priority).await,
+        None => Err(error),
+    }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MonStat {
     HP,
     Attack,
@@ -47,21 +63,31 @@ impl FromStr for MonStat {
     }
 }
 
-impl ToString for MonStat {
-    fn to_string(&self) -> String {
+impl fmt::Display for MonStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::HP => "hp".to_owned(),
-            Self::Attack => "attack".to_owned(),
-            Self::Defense => "defense".to_owned(),
-            Self::SpecialAttack => "special attack".to_owned(),
-            Self::SpecialDefense => "special defense".to_owned(),
-            Self::Speed => "speed".to_owned(),
+            Self::HP => write!(f, "hp"),
+            Self::Attack => write!(f, "attack"),
+            Self::Defense => write!(f, "defense"),
+            Self::SpecialAttack => write!(f, "special attack"),
+            Self::SpecialDefense => write!(f, "special defense"),
+            Self::Speed => write!(f, "speed"),
         }
     }
 }
 
 impl MonStat {
-    pub fn to_short_string(&self) -> String {
+    /// In the order PokeAPI lists them
+    pub const ALL: [MonStat; 6] = [
+        MonStat::HP,
+        MonStat::Attack,
+        MonStat::Defense,
+        MonStat::SpecialAttack,
+        MonStat::SpecialDefense,
+        MonStat::Speed,
+    ];
+
+    pub fn to_short_string(self) -> String {
         match self {
             Self::HP => "HP".to_owned(),
             Self::Attack => "ATK".to_owned(),
@@ -84,6 +110,7 @@ impl MonStat {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MonType {
     Normal,
     Fire,
@@ -133,27 +160,27 @@ impl FromStr for MonType {
     }
 }
 
-impl ToString for MonType {
-    fn to_string(&self) -> String {
+impl fmt::Display for MonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            MonType::Normal => "normal".to_owned(),
-            MonType::Fire => "fire".to_owned(),
-            MonType::Water => "water".to_owned(),
-            MonType::Electric => "electric".to_owned(),
-            MonType::Grass => "grass".to_owned(),
-            MonType::Ice => "ice".to_owned(),
-            MonType::Fighting => "fighting".to_owned(),
-            MonType::Poison => "poison".to_owned(),
-            MonType::Ground => "ground".to_owned(),
-            MonType::Flying => "flying".to_owned(),
-            MonType::Psychic => "psychic".to_owned(),
-            MonType::Bug => "bug".to_owned(),
-            MonType::Rock => "rock".to_owned(),
-            MonType::Ghost => "ghost".to_owned(),
-            MonType::Dragon => "dragon".to_owned(),
-            MonType::Dark => "dark".to_owned(),
-            MonType::Steel => "steel".to_owned(),
-            MonType::Fairy => "fairy".to_owned(),
+            MonType::Normal => write!(f, "normal"),
+            MonType::Fire => write!(f, "fire"),
+            MonType::Water => write!(f, "water"),
+            MonType::Electric => write!(f, "electric"),
+            MonType::Grass => write!(f, "grass"),
+            MonType::Ice => write!(f, "ice"),
+            MonType::Fighting => write!(f, "fighting"),
+            MonType::Poison => write!(f, "poison"),
+            MonType::Ground => write!(f, "ground"),
+            MonType::Flying => write!(f, "flying"),
+            MonType::Psychic => write!(f, "psychic"),
+            MonType::Bug => write!(f, "bug"),
+            MonType::Rock => write!(f, "rock"),
+            MonType::Ghost => write!(f, "ghost"),
+            MonType::Dragon => write!(f, "dragon"),
+            MonType::Dark => write!(f, "dark"),
+            MonType::Steel => write!(f, "steel"),
+            MonType::Fairy => write!(f, "fairy"),
         }
     }
 }
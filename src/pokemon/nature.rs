@@ -0,0 +1,145 @@
+use std::{fmt, str::FromStr};
+
+use super::MonStat;
+
+/// Natures in their in-game index order, each one raises a stat by 10% and lowers another by 10%
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Nature {
+    #[default]
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+/// Stats natures can change, the index of a nature is `5 * raised + lowered` over these
+pub const AFFECTED: [MonStat; 5] = [
+    MonStat::Attack,
+    MonStat::Defense,
+    MonStat::Speed,
+    MonStat::SpecialAttack,
+    MonStat::SpecialDefense,
+];
+
+impl Nature {
+    pub const ALL: [Nature; 25] = [
+        Nature::Hardy,
+        Nature::Lonely,
+        Nature::Brave,
+        Nature::Adamant,
+        Nature::Naughty,
+        Nature::Bold,
+        Nature::Docile,
+        Nature::Relaxed,
+        Nature::Impish,
+        Nature::Lax,
+        Nature::Timid,
+        Nature::Hasty,
+        Nature::Serious,
+        Nature::Jolly,
+        Nature::Naive,
+        Nature::Modest,
+        Nature::Mild,
+        Nature::Quiet,
+        Nature::Bashful,
+        Nature::Rash,
+        Nature::Calm,
+        Nature::Gentle,
+        Nature::Sassy,
+        Nature::Careful,
+        Nature::Quirky,
+    ];
+
+    /// The nature after this one, wrapping back to the first
+    pub fn next(self) -> Self {
+        Nature::ALL[(self as usize + 1) % Nature::ALL.len()]
+    }
+
+    /// The nature before this one, wrapping back to the last
+    pub fn previous(self) -> Self {
+        Nature::ALL[(self as usize + Nature::ALL.len() - 1) % Nature::ALL.len()]
+    }
+
+    /// None for the five neutral natures
+    pub fn raised(self) -> Option<MonStat> {
+        let (raised, lowered) = (self as usize / 5, self as usize % 5);
+        (raised != lowered).then_some(AFFECTED[raised])
+    }
+
+    /// None for the five neutral natures
+    pub fn lowered(self) -> Option<MonStat> {
+        let (raised, lowered) = (self as usize / 5, self as usize % 5);
+        (raised != lowered).then_some(AFFECTED[lowered])
+    }
+
+    /// Multiplier for `stat` in tenths, 11 when raised and 9 when lowered
+    pub fn modifier(self, stat: MonStat) -> u32 {
+        if self.raised() == Some(stat) {
+            11
+        } else if self.lowered() == Some(stat) {
+            9
+        } else {
+            10
+        }
+    }
+}
+
+impl FromStr for Nature {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Nature::ALL
+            .into_iter()
+            .find(|nature| nature.to_string().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for Nature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natures_raise_and_lower_the_right_stats() {
+        assert_eq!(Nature::Adamant.raised(), Some(MonStat::Attack));
+        assert_eq!(Nature::Adamant.lowered(), Some(MonStat::SpecialAttack));
+        assert_eq!(Nature::Timid.raised(), Some(MonStat::Speed));
+        assert_eq!(Nature::Timid.lowered(), Some(MonStat::Attack));
+        assert_eq!(Nature::Careful.raised(), Some(MonStat::SpecialDefense));
+        assert_eq!(Nature::Careful.lowered(), Some(MonStat::SpecialAttack));
+        for neutral in [Nature::Hardy, Nature::Docile, Nature::Serious, Nature::Bashful, Nature::Quirky] {
+            assert_eq!(neutral.raised(), None);
+            assert_eq!(neutral.modifier(MonStat::Attack), 10);
+        }
+        assert_eq!("modest".parse(), Ok(Nature::Modest));
+        assert_eq!(Nature::Quirky.next(), Nature::Hardy);
+        assert_eq!(Nature::Hardy.previous(), Nature::Quirky);
+    }
+}
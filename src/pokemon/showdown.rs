@@ -0,0 +1,396 @@
+use std::fmt;
+
+use http::StatusCode;
+
+use crate::fetch::{FetchError, Priority, Scheduler};
+
+use super::{
+    stats::{StatSpread, MAX_EV, MAX_IV, MAX_TOTAL_EVS},
+    team::{TeamMember, MAX_MOVES, TEAM_SIZE},
+    fetch_pokemon_or_species, MonStat,
+};
+
+/// Lines Showdown writes that don't matter here
+const IGNORED: [&str; 7] = ["Shiny:", "Happiness:", "Tera Type:", "Gigantamax:", "Dynamax Level:", "Pokeball:", "Hidden Power:"];
+
+/// A set from a paste, with the lines its names came from so checking them can point back
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShowdownSet {
+    pub member: TeamMember,
+    /// Line of the species, counting from 1
+    pub line: usize,
+    pub ability_line: Option<usize>,
+    /// Line of each move, in the same order as the member's moves
+    pub move_lines: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShowdownError {
+    /// Counting from 1
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ShowdownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {0}: {1}", self.line, self.message)
+    }
+}
+
+/// Names Showdown writes with punctuation their PokeAPI identifier doesn't keep
+const NAMES: [(&str, &str); 34] = [
+    ("baby-doll-eyes", "Baby-Doll Eyes"),
+    ("double-edge", "Double-Edge"),
+    ("forests-curse", "Forest's Curse"),
+    ("freeze-dry", "Freeze-Dry"),
+    ("kings-shield", "King's Shield"),
+    ("lands-wrath", "Land's Wrath"),
+    ("lock-on", "Lock-On"),
+    ("mud-slap", "Mud-Slap"),
+    ("multi-attack", "Multi-Attack"),
+    ("natures-madness", "Nature's Madness"),
+    ("power-up-punch", "Power-Up Punch"),
+    ("self-destruct", "Self-Destruct"),
+    ("soft-boiled", "Soft-Boiled"),
+    ("topsy-turvy", "Topsy-Turvy"),
+    ("trick-or-treat", "Trick-or-Treat"),
+    ("u-turn", "U-turn"),
+    ("v-create", "V-create"),
+    ("wake-up-slap", "Wake-Up Slap"),
+    ("will-o-wisp", "Will-O-Wisp"),
+    ("x-scissor", "X-Scissor"),
+    ("kings-rock", "King's Rock"),
+    ("never-melt-ice", "Never-Melt Ice"),
+    ("farfetchd", "Farfetch’d"),
+    ("farfetchd-galar", "Farfetch’d-Galar"),
+    ("flabebe", "Flabébé"),
+    ("hakamo-o", "Hakamo-o"),
+    ("jangmo-o", "Jangmo-o"),
+    ("kommo-o", "Kommo-o"),
+    ("mime-jr", "Mime Jr."),
+    ("mr-mime", "Mr. Mime"),
+    ("mr-mime-galar", "Mr. Mime-Galar"),
+    ("mr-rime", "Mr. Rime"),
+    ("sirfetchd", "Sirfetch’d"),
+    ("type-null", "Type: Null"),
+];
+
+/// PokeAPI identifier for a display name, like "King's Shield" to "kings-shield"
+pub fn to_id(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .map(fold_accent)
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect()
+}
+
+/// The unaccented letter for a lowercase accented one, like the é in "Flabébé"
+fn fold_accent(c: char) -> char {
+    match c {
+        'à'..='å' => 'a',
+        'ç' => 'c',
+        'è'..='ë' => 'e',
+        'ì'..='ï' => 'i',
+        'ñ' => 'n',
+        'ò'..='ö' => 'o',
+        'ù'..='ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        c => c,
+    }
+}
+
+/// Display name for a PokeAPI identifier, words are joined with `separator` unless Showdown spells
+/// the name some other way
+fn display_name(id: &str, separator: &str) -> String {
+    if let Some((_, name)) = NAMES.iter().find(|(known, _)| *known == id) {
+        return (*name).to_owned();
+    }
+    id.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn stat_name(stat: MonStat) -> &'static str {
+    match stat {
+        MonStat::HP => "HP",
+        MonStat::Attack => "Atk",
+        MonStat::Defense => "Def",
+        MonStat::SpecialAttack => "SpA",
+        MonStat::SpecialDefense => "SpD",
+        MonStat::Speed => "Spe",
+    }
+}
+
+/// "252 Atk / 4 SpD" into per stat values, anything missing is left alone
+fn parse_spread(text: &str, values: &mut [u32; 6], max: u32) -> Result<(), String> {
+    for part in text.split('/') {
+        let (value, stat) = part
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| format!("expected a value and a stat, got \"{}\"", part.trim()))?;
+        let value: u32 = value.parse().map_err(|_| format!("\"{value}\" is not a number"))?;
+        if value > max {
+            return Err(format!("{value} is more than {max}"));
+        }
+        let index = MonStat::ALL
+            .iter()
+            .position(|s| stat_name(*s).eq_ignore_ascii_case(stat.trim()))
+            .ok_or_else(|| format!("unknown stat \"{}\"", stat.trim()))?;
+        values[index] = value;
+    }
+    Ok(())
+}
+
+/// First line of a set: `Nickname (Species) (M) @ Item`, where everything but the species is optional
+fn parse_species_line(line: &str) -> (String, Option<String>) {
+    let (name, item) = match line.split_once(" @ ") {
+        Some((name, item)) => (name.trim(), Some(to_id(item))),
+        None => (line.trim(), None),
+    };
+    let name = name.strip_suffix(" (M)").or_else(|| name.strip_suffix(" (F)")).unwrap_or(name);
+    let species = match (name.rfind(" ("), name.ends_with(')')) {
+        (Some(open), true) => &name[open + 2..name.len() - 1],
+        _ => name,
+    };
+    (to_id(species), item)
+}
+
+/// Parses a whole paste, sets are separated by blank lines. Every problem is reported, not just the first
+pub fn parse(text: &str) -> Result<Vec<ShowdownSet>, Vec<ShowdownError>> {
+    let mut sets: Vec<ShowdownSet> = Vec::new();
+    let mut errors = Vec::new();
+    let mut current: Option<ShowdownSet> = None;
+    // Terminals send pasted line breaks as carriage returns
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    for (i, raw) in text.lines().enumerate() {
+        let (line_number, line) = (i + 1, raw.trim());
+        let mut error = |message: String| errors.push(ShowdownError { line: line_number, message });
+        // Teambuilder backups put a header before each team
+        if line.starts_with("===") {
+            continue;
+        }
+        if line.is_empty() {
+            sets.extend(current.take());
+            continue;
+        }
+        let Some(set) = current.as_mut() else {
+            if sets.len() == TEAM_SIZE {
+                error(format!("a team has at most {TEAM_SIZE} Pokémon"));
+            }
+            let (species, item) = parse_species_line(line);
+            current = Some(ShowdownSet {
+                member: TeamMember { pokemon: species, item, spread: StatSpread { level: 100, ..Default::default() }, ..Default::default() },
+                line: line_number,
+                ability_line: None,
+                move_lines: Vec::new(),
+            });
+            continue;
+        };
+        let member = &mut set.member;
+        if let Some(move_) = line.strip_prefix('-').or_else(|| line.strip_prefix('~')) {
+            // Hidden Power's type is written after the name
+            let move_ = move_.split('[').next().unwrap_or_default();
+            if member.moves.len() == MAX_MOVES {
+                error(format!("a Pokémon knows at most {MAX_MOVES} moves"));
+            }
+            member.moves.push(to_id(move_));
+            set.move_lines.push(line_number);
+        } else if let Some(ability) = line.strip_prefix("Ability:") {
+            member.ability = Some(to_id(ability));
+            set.ability_line = Some(line_number);
+        } else if let Some(level) = line.strip_prefix("Level:") {
+            match level.trim().parse() {
+                Ok(level @ 1..=100) => member.spread.level = level,
+                _ => error(format!("\"{}\" is not a level between 1 and 100", level.trim())),
+            }
+        } else if let Some(evs) = line.strip_prefix("EVs:") {
+            member.spread.evs = [0; 6];
+            if let Err(message) = parse_spread(evs, &mut member.spread.evs, MAX_EV) {
+                error(message);
+            } else if member.spread.total_evs() > MAX_TOTAL_EVS {
+                error(format!("{} EVs is more than {MAX_TOTAL_EVS}", member.spread.total_evs()));
+            }
+        } else if let Some(ivs) = line.strip_prefix("IVs:") {
+            if let Err(message) = parse_spread(ivs, &mut member.spread.ivs, MAX_IV) {
+                error(message);
+            }
+        } else if let Some(nature) = line.strip_suffix(" Nature") {
+            match nature.trim().parse() {
+                Ok(nature) => member.spread.nature = nature,
+                Err(()) => error(format!("unknown nature \"{}\"", nature.trim())),
+            }
+        } else if !IGNORED.iter().any(|prefix| line.starts_with(prefix)) {
+            error(format!("unrecognised line \"{line}\""));
+        }
+    }
+    sets.extend(current);
+    if errors.is_empty() { Ok(sets) } else { Err(errors) }
+}
+
+/// Writes members the way Showdown exports them, leaving out defaults
+pub fn export<'a>(members: impl IntoIterator<Item = &'a TeamMember>) -> String {
+    let sets: Vec<String> = members
+        .into_iter()
+        .map(|member| {
+            let mut lines = vec![match &member.item {
+                Some(item) => format!("{} @ {}", display_name(&member.pokemon, "-"), display_name(item, " ")),
+                None => display_name(&member.pokemon, "-"),
+            }];
+            if let Some(ability) = &member.ability {
+                lines.push(format!("Ability: {}", display_name(ability, " ")));
+            }
+            if member.spread.level != 100 {
+                lines.push(format!("Level: {}", member.spread.level));
+            }
+            let spread = |values: &[u32; 6], skip: u32| {
+                let parts: Vec<String> = MonStat::ALL
+                    .iter()
+                    .zip(values)
+                    .filter(|(_, value)| **value != skip)
+                    .map(|(stat, value)| format!("{value} {}", stat_name(*stat)))
+                    .collect();
+                parts.join(" / ")
+            };
+            if member.spread.total_evs() > 0 {
+                lines.push(format!("EVs: {}", spread(&member.spread.evs, 0)));
+            }
+            lines.push(format!("{} Nature", member.spread.nature));
+            if member.spread.ivs.iter().any(|iv| *iv != MAX_IV) {
+                lines.push(format!("IVs: {}", spread(&member.spread.ivs, MAX_IV)));
+            }
+            lines.extend(member.moves.iter().map(|move_| format!("- {}", display_name(move_, " "))));
+            lines.join("\n")
+        })
+        .collect();
+    sets.join("\n\n") + "\n"
+}
+
+/// Checks every name against PokeAPI: species may be given by species name, in which case the
+/// default variety is used, and abilities and moves have to be ones the Pokémon can have
+pub async fn validate(scheduler: &Scheduler, sets: Vec<ShowdownSet>) -> Result<Vec<TeamMember>, Vec<ShowdownError>> {
+    let mut members = Vec::new();
+    let mut errors = Vec::new();
+    for set in sets {
+        let mut error = |line: usize, message: String| errors.push(ShowdownError { line, message });
+        let name = &set.member.pokemon;
+        let mon = match fetch_pokemon_or_species(name, scheduler, Priority::Visible).await {
+            Ok(mon) => mon,
+            Err(FetchError::Status(StatusCode::NOT_FOUND)) => {
+                error(set.line, format!("no Pokémon called \"{name}\""));
+                continue;
+            }
+            Err(err) => {
+                error(set.line, format!("couldn't look up \"{name}\": {err}"));
+                continue;
+            }
+        };
+        if let (Some(ability), Some(line)) = (&set.member.ability, set.ability_line)
+            && !mon.abilities.iter().flat_map(|a| &a.ability).any(|a| a.name == *ability)
+        {
+            error(line, format!("{} can't have the ability \"{ability}\"", mon.name));
+        }
+        for (move_, line) in set.member.moves.iter().zip(&set.move_lines) {
+            if !mon.moves.iter().any(|m| m.move_.name == *move_) {
+                error(*line, format!("{} can't learn \"{move_}\"", mon.name));
+            }
+        }
+        members.push(TeamMember { pokemon: mon.name, ..set.member });
+    }
+    if errors.is_empty() { Ok(members) } else { Err(errors) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokemon::nature::Nature;
+
+    const GARCHOMP: &str = "\
+Garchomp @ Choice Scarf
+Ability: Rough Skin
+Level: 50
+EVs: 4 HP / 252 Atk / 252 Spe
+Jolly Nature
+IVs: 0 SpA
+- Earthquake
+- Dragon Claw
+- U-turn
+- Stone Edge
+";
+
+    #[test]
+    fn parses_a_set() {
+        let sets = parse(GARCHOMP).unwrap();
+        assert_eq!(sets.len(), 1);
+        let set = &sets[0];
+        assert_eq!(set.member.pokemon, "garchomp");
+        assert_eq!(set.member.item.as_deref(), Some("choice-scarf"));
+        assert_eq!(set.member.ability.as_deref(), Some("rough-skin"));
+        assert_eq!(set.member.spread.level, 50);
+        assert_eq!(set.member.spread.nature, Nature::Jolly);
+        assert_eq!(set.member.spread.evs, [4, 252, 0, 0, 0, 252]);
+        assert_eq!(set.member.spread.ivs, [31, 31, 31, 0, 31, 31]);
+        assert_eq!(set.member.moves, ["earthquake", "dragon-claw", "u-turn", "stone-edge"]);
+        assert_eq!((set.line, set.ability_line, set.move_lines.clone()), (1, Some(2), vec![7, 8, 9, 10]));
+    }
+
+    #[test]
+    fn round_trips() {
+        let sets = parse(GARCHOMP).unwrap();
+        assert_eq!(export(sets.iter().map(|set| &set.member)), GARCHOMP);
+        let again = parse(&export(sets.iter().map(|set| &set.member))).unwrap();
+        assert_eq!(again[0].member, sets[0].member);
+        let pasted = parse(&GARCHOMP.replace('\n', "\r")).unwrap();
+        assert_eq!(pasted[0].member, sets[0].member);
+    }
+
+    #[test]
+    fn nicknames_genders_and_several_sets() {
+        let text = "=== [gen9] Team ===\n\nChompy (Garchomp) (F) @ Life Orb\n- Earthquake\n\nMr. Mime\nTimid Nature\nTera Type: Fairy\n- Psychic\n";
+        let sets = parse(text).unwrap();
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].member.pokemon, "garchomp");
+        assert_eq!(sets[0].member.item.as_deref(), Some("life-orb"));
+        assert_eq!(sets[0].member.spread.level, 100);
+        assert_eq!(sets[1].member.pokemon, "mr-mime");
+        assert_eq!(sets[1].move_lines, [9]);
+    }
+
+    #[test]
+    fn reports_every_error_with_its_line() {
+        let text = "Garchomp\nLevel: 101\nEVs: 252 Atk / 252 Spe / 252 HP\nGrumpy Nature\nTotally not a line\n";
+        let errors = parse(text).unwrap_err();
+        let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [2, 3, 4, 5]);
+        assert_eq!(errors[2].to_string(), "line 4: unknown nature \"Grumpy\"");
+    }
+
+    #[test]
+    fn showdown_names_read_back_as_their_identifier() {
+        for (id, name) in NAMES {
+            assert_eq!(to_id(name), id);
+        }
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(to_id("King's Shield"), "kings-shield");
+        assert_eq!(to_id("Farfetch’d"), "farfetchd");
+        assert_eq!(to_id("Type: Null"), "type-null");
+        assert_eq!(to_id(" Will-O-Wisp "), "will-o-wisp");
+        assert_eq!(to_id("Flabébé"), "flabebe");
+        assert_eq!(to_id("Poké Ball"), "poke-ball");
+        assert_eq!(display_name("deoxys-attack", "-"), "Deoxys-Attack");
+        assert_eq!(display_name("choice-scarf", " "), "Choice Scarf");
+        assert_eq!(display_name(&to_id("Will-O-Wisp"), " "), "Will-O-Wisp");
+        assert_eq!(display_name(&to_id("Mr. Mime"), "-"), "Mr. Mime");
+        assert_eq!(display_name(&to_id("King's Shield"), " "), "King's Shield");
+    }
+}
@@ -0,0 +1,171 @@
+use std::str::FromStr;
+
+use rustemon::model::pokemon::PokemonStat;
+
+use super::{nature::Nature, MonStat};
+
+pub const MAX_IV: u32 = 31;
+pub const MAX_EV: u32 = 252;
+/// Total EVs a Pokémon can have across all its stats
+pub const MAX_TOTAL_EVS: u32 = 510;
+
+/// Everything besides the base stats that goes into a Pokémon's actual stats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatSpread {
+    pub level: u32,
+    pub nature: Nature,
+    /// Indexed like [`MonStat::ALL`]
+    pub ivs: [u32; 6],
+    /// Indexed like [`MonStat::ALL`]
+    pub evs: [u32; 6],
+}
+
+impl Default for StatSpread {
+    fn default() -> Self {
+        Self {
+            level: 50,
+            nature: Nature::default(),
+            ivs: [MAX_IV; 6],
+            evs: [0; 6],
+        }
+    }
+}
+
+impl StatSpread {
+    pub fn total_evs(&self) -> u32 {
+        self.evs.iter().sum()
+    }
+
+    /// One level up or down, between 1 and 100
+    pub fn step_level(&mut self, increase: bool) {
+        self.level = if increase { u32::min(self.level + 1, 100) } else { u32::max(self.level.saturating_sub(1), 1) };
+    }
+
+    /// Four EVs up or down in `stat` since EVs only count in steps of 4, the total stays capped
+    pub fn step_ev(&mut self, stat: MonStat, increase: bool) {
+        let i = MonStat::ALL.iter().position(|s| *s == stat).unwrap_or(0);
+        let left = MAX_TOTAL_EVS - self.total_evs() + self.evs[i];
+        self.evs[i] = if increase {
+            u32::min(self.evs[i] + 4, u32::min(MAX_EV, left))
+        } else {
+            self.evs[i].saturating_sub(4)
+        };
+    }
+
+    pub fn step_nature(&mut self, increase: bool) {
+        self.nature = if increase { self.nature.next() } else { self.nature.previous() };
+    }
+
+    /// Actual stats for the given base stats, indexed like [`MonStat::ALL`]
+    pub fn stats(&self, base: [u32; 6]) -> [u32; 6] {
+        std::array::from_fn(|i| {
+            let stat = MonStat::ALL[i];
+            calc_stat(stat, base[i], self.ivs[i], self.evs[i], self.level, self.nature)
+        })
+    }
+}
+
+/// PokeAPI's base stats indexed like [`MonStat::ALL`], missing stats are 0
+pub fn base_stats(stats: &[PokemonStat]) -> [u32; 6] {
+    let mut base = [0; 6];
+    for stat in stats {
+        if let Ok(mon_stat) = MonStat::from_str(&stat.stat.name)
+            && let Some(i) = MonStat::ALL.iter().position(|s| *s == mon_stat)
+        {
+            base[i] = stat.base_stat as u32;
+        }
+    }
+    base
+}
+
+/// Gen 3+ stat formula, HP skips the nature and adds the level instead of a flat 5
+pub fn calc_stat(stat: MonStat, base: u32, iv: u32, ev: u32, level: u32, nature: Nature) -> u32 {
+    let core = (2 * base + iv + ev / 4) * level / 100;
+    match stat {
+        // Shedinja always has 1 HP
+        MonStat::HP if base == 1 => 1,
+        MonStat::HP => core + level + 10,
+        stat => (core + 5) * nature.modifier(stat) / 10,
+    }
+}
+
+/// Lowest and highest possible value of a stat at `level`, from 0 IVs, 0 EVs and a hindering
+/// nature up to 31 IVs, 252 EVs and a helpful one
+pub fn stat_range(stat: MonStat, base: u32, level: u32) -> (u32, u32) {
+    let (hindering, helpful) = match stat {
+        MonStat::HP => (Nature::Hardy, Nature::Hardy),
+        stat => {
+            let find = |modifier| {
+                Nature::ALL
+                    .into_iter()
+                    .find(|nature| nature.modifier(stat) == modifier)
+                    .unwrap_or_default()
+            };
+            (find(9), find(11))
+        }
+    };
+    (
+        calc_stat(stat, base, 0, 0, level, hindering),
+        calc_stat(stat, base, MAX_IV, MAX_EV, level, helpful),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GARCHOMP: [u32; 6] = [108, 130, 95, 80, 85, 102];
+
+    #[test]
+    fn matches_the_worked_example() {
+        // Level 78 Garchomp from the Bulbapedia stat article, without its Adamant nature
+        let spread = StatSpread {
+            level: 78,
+            nature: Nature::Hardy,
+            ivs: [24, 12, 30, 16, 23, 5],
+            evs: [74, 190, 91, 48, 84, 23],
+        };
+        assert_eq!(spread.stats(GARCHOMP), [289, 253, 193, 151, 171, 171]);
+    }
+
+    #[test]
+    fn hp_adds_the_level() {
+        assert_eq!(calc_stat(MonStat::HP, 108, 31, 252, 100, Nature::Hardy), 420);
+        assert_eq!(calc_stat(MonStat::Attack, 108, 31, 252, 100, Nature::Hardy), 315);
+        assert_eq!(calc_stat(MonStat::HP, 1, 31, 252, 100, Nature::Hardy), 1);
+    }
+
+    #[test]
+    fn adamant_raises_attack_and_lowers_special_attack() {
+        // The same level 78 Garchomp with the Adamant nature it has in the article
+        let spread = StatSpread {
+            level: 78,
+            nature: Nature::Adamant,
+            ivs: [24, 12, 30, 16, 23, 5],
+            evs: [74, 190, 91, 48, 84, 23],
+        };
+        assert_eq!(spread.stats(GARCHOMP), [289, 278, 193, 135, 171, 171]);
+    }
+
+    #[test]
+    fn hp_ignores_the_nature() {
+        let hp = |nature| calc_stat(MonStat::HP, 108, 31, 252, 100, nature);
+        assert_eq!(hp(Nature::Hardy), 420);
+        assert_eq!(hp(Nature::Lonely), 420);
+    }
+
+    #[test]
+    fn ranges_at_50_and_100() {
+        assert_eq!(stat_range(MonStat::HP, 108, 50), (168, 215));
+        assert_eq!(stat_range(MonStat::HP, 108, 100), (326, 420));
+        assert_eq!(stat_range(MonStat::Attack, 130, 50), (121, 200));
+        assert_eq!(stat_range(MonStat::Attack, 130, 100), (238, 394));
+    }
+
+    #[test]
+    fn default_spread_is_level_50_with_perfect_ivs() {
+        let spread = StatSpread::default();
+        assert_eq!(spread.total_evs(), 0);
+        assert_eq!(spread.stats(GARCHOMP), [183, 150, 115, 100, 105, 122]);
+    }
+}
@@ -0,0 +1,151 @@
+use super::{effectiveness::TypeChart, stats::StatSpread, MonType};
+
+pub const TEAM_SIZE: usize = 6;
+pub const MAX_MOVES: usize = 4;
+
+/// One Pokémon of a team, everything is kept by PokeAPI name
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TeamMember {
+    pub pokemon: String,
+    pub ability: Option<String>,
+    pub item: Option<String>,
+    pub spread: StatSpread,
+    pub moves: Vec<String>,
+}
+
+impl TeamMember {
+    pub fn new(pokemon: String) -> Self {
+        Self {
+            pokemon,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeamError {
+    TooManyMoves,
+    AlreadyKnown,
+    /// The Pokémon can't learn the move in any game
+    Illegal { pokemon: String, move_: String },
+}
+
+impl std::fmt::Display for TeamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TeamError::TooManyMoves => write!(f, "Already knows {MAX_MOVES} moves"),
+            TeamError::AlreadyKnown => write!(f, "Already knows that move"),
+            TeamError::Illegal { pokemon, move_ } => write!(f, "{pokemon} can't learn {move_}"),
+        }
+    }
+}
+
+/// Adds `move_` if `learnable` has it, which should be every move the Pokémon learns in any game
+pub fn add_move(member: &mut TeamMember, move_: &str, learnable: &[&str]) -> Result<(), TeamError> {
+    if member.moves.iter().any(|known| known == move_) {
+        return Err(TeamError::AlreadyKnown);
+    }
+    if member.moves.len() >= MAX_MOVES {
+        return Err(TeamError::TooManyMoves);
+    }
+    if !learnable.contains(&move_) {
+        return Err(TeamError::Illegal { pokemon: member.pokemon.clone(), move_: move_.to_owned() });
+    }
+    member.moves.push(move_.to_owned());
+    Ok(())
+}
+
+/// Types at least one of `move_types` hits super effectively, in the chart's order
+pub fn coverage(move_types: &[MonType], chart: TypeChart) -> Vec<MonType> {
+    chart
+        .types()
+        .filter(|defending| move_types.iter().any(|t| t.multiplier_against(*defending, chart) > 1.0))
+        .collect()
+}
+
+/// How a team fares against one attacking type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TeamMatchup {
+    pub attacking: MonType,
+    pub weak: usize,
+    /// Resistances and immunities
+    pub resist: usize,
+}
+
+/// Attacking types that hit at least one member super effectively, the worst first
+pub fn weaknesses(members: &[Vec<MonType>], chart: TypeChart) -> Vec<TeamMatchup> {
+    let mut matchups: Vec<TeamMatchup> = chart
+        .types()
+        .map(|attacking| {
+            let multipliers = members.iter().map(|types| attacking.multiplier_against_types(types, chart));
+            TeamMatchup {
+                attacking,
+                weak: multipliers.clone().filter(|m| *m > 1.0).count(),
+                resist: multipliers.filter(|m| *m < 1.0).count(),
+            }
+        })
+        .filter(|matchup| matchup.weak > 0)
+        .collect();
+    matchups.sort_by_key(|matchup| std::cmp::Reverse(matchup.weak as isize - matchup.resist as isize));
+    matchups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MonType::*;
+
+    #[test]
+    fn add_move_checks_the_moveset() {
+        let learnable = ["thunderbolt", "thunder", "volt-tackle", "surf", "iron-tail", "quick-attack"];
+        let mut pikachu = TeamMember::new("pikachu".to_owned());
+        assert_eq!(add_move(&mut pikachu, "thunderbolt", &learnable), Ok(()));
+        assert_eq!(add_move(&mut pikachu, "thunderbolt", &learnable), Err(TeamError::AlreadyKnown));
+        assert_eq!(
+            add_move(&mut pikachu, "flamethrower", &learnable),
+            Err(TeamError::Illegal { pokemon: "pikachu".to_owned(), move_: "flamethrower".to_owned() })
+        );
+        for move_ in ["thunder", "volt-tackle", "surf"] {
+            assert_eq!(add_move(&mut pikachu, move_, &learnable), Ok(()));
+        }
+        assert_eq!(add_move(&mut pikachu, "iron-tail", &learnable), Err(TeamError::TooManyMoves));
+        // A fifth move is refused even when it isn't learnable
+        assert_eq!(add_move(&mut pikachu, "flamethrower", &learnable), Err(TeamError::TooManyMoves));
+        assert_eq!(pikachu.moves, ["thunderbolt", "thunder", "volt-tackle", "surf"]);
+    }
+
+    #[test]
+    fn coverage_follows_the_chart() {
+        assert_eq!(coverage(&[Fire], TypeChart::Gen6On), [Grass, Ice, Bug, Steel]);
+        // Steel doesn't exist yet
+        assert_eq!(coverage(&[Fire], TypeChart::Gen1), [Grass, Ice, Bug]);
+        assert_eq!(coverage(&[Fire, Water], TypeChart::Gen6On), [Fire, Grass, Ice, Ground, Bug, Rock, Steel]);
+        assert!(coverage(&[], TypeChart::Gen6On).is_empty());
+    }
+
+    fn matchup(attacking: MonType, weak: usize, resist: usize) -> TeamMatchup {
+        TeamMatchup { attacking, weak, resist }
+    }
+
+    #[test]
+    fn weaknesses_are_worst_first() {
+        let team = [vec![Fire], vec![Fire, Flying]];
+        // Ground is cancelled out by Flying's immunity and goes last
+        assert_eq!(
+            weaknesses(&team, TypeChart::Gen6On),
+            [matchup(Water, 2, 0), matchup(Rock, 2, 0), matchup(Electric, 1, 0), matchup(Ground, 1, 1)]
+        );
+        // Fire didn't resist Ice in Gen 1
+        assert_eq!(
+            weaknesses(&team, TypeChart::Gen1),
+            [
+                matchup(Water, 2, 0),
+                matchup(Rock, 2, 0),
+                matchup(Electric, 1, 0),
+                matchup(Ice, 1, 0),
+                matchup(Ground, 1, 1),
+            ]
+        );
+        assert!(weaknesses(&[], TypeChart::Gen6On).is_empty());
+    }
+}